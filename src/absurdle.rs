@@ -0,0 +1,138 @@
+//! Absurdle, where the host has no secret answer and keeps as many answers alive as it can.
+
+use std::collections::BTreeMap;
+
+use crate::strategy::Sample;
+use crate::{Feedback, Guess, PatternMatrix, ScoreContext, Strategy, WorstCase, feedback, pattern};
+
+/// The feedback the host gives `guess` and the answers it keeps: the largest group of
+/// `possible` answers that share a feedback, ties going to the lowest
+/// [`pattern_index`](crate::pattern_index).
+pub fn host_reply<'a>(guess: &str, possible: &[&'a str]) -> (Vec<Feedback>, Vec<&'a str>) {
+    let guess_chars = guess.chars().collect::<Vec<_>>();
+
+    let mut buckets: BTreeMap<usize, (Vec<Feedback>, Vec<&str>)> = BTreeMap::new();
+    for &answer in possible {
+        let answer_chars = answer.chars().collect::<Vec<_>>();
+        buckets
+            .entry(pattern(&guess_chars, &answer_chars))
+            .or_insert_with(|| (feedback(&guess_chars, &answer_chars), Vec::new()))
+            .1
+            .push(answer);
+    }
+
+    // `max_by_key` keeps the last of equal elements, so look from the highest pattern down.
+    buckets
+        .into_values()
+        .rev()
+        .max_by_key(|(_, kept)| kept.len())
+        .unwrap_or_else(|| (vec![Feedback::Grey; guess_chars.len()], Vec::new()))
+}
+
+/// Colors every complete guess the way the host would, starting from `answers`, and returns
+/// the answers that survive all of them.
+pub fn host_replies<'a>(guesses: &mut [Guess], answers: &[&'a str]) -> Vec<&'a str> {
+    let mut possible = answers.to_vec();
+    for guess in guesses.iter_mut().filter(|guess| guess.is_complete()) {
+        let (feedback, kept) = host_reply(&guess.word, &possible);
+        guess.feedback = feedback;
+        possible = kept;
+    }
+
+    possible
+}
+
+/// The most answers the host can keep after a guess, counted over every answer. [`Minimax`]
+/// scores the same but estimates it from a sample for long lists, which can miss the group the
+/// host keeps.
+///
+/// [`Minimax`]: crate::Minimax
+pub struct HostKeeps;
+
+impl Strategy for HostKeeps {
+    fn name(&self) -> &'static str {
+        "absurdle"
+    }
+
+    fn label(&self) -> &'static str {
+        "Most words the host keeps"
+    }
+
+    fn lower_is_better(&self) -> bool {
+        true
+    }
+
+    fn uses_patterns(&self) -> bool {
+        true
+    }
+
+    fn scores(&self, guesses: &[&str], possible: &[&str], context: ScoreContext) -> Vec<f64> {
+        self.scores_with_worst_cases(guesses, possible, context)
+            .into_iter()
+            .map(|(score, _)| score)
+            .collect()
+    }
+
+    fn scores_with_worst_cases(
+        &self,
+        guesses: &[&str],
+        possible: &[&str],
+        context: ScoreContext,
+    ) -> Vec<(f64, Option<WorstCase>)> {
+        let sample = Sample::new(possible, context, usize::MAX);
+
+        sample.scores(guesses, |groups| {
+            groups.iter().map(|&(size, _)| size).max().unwrap_or(0) as f64
+        })
+    }
+}
+
+/// `guesses` ranked by the most answers the host can keep after them, best first, as positions
+/// in `guesses`. Patterns are looked up in `patterns` if given.
+pub fn rank(
+    guesses: &[&str],
+    possible: &[&str],
+    patterns: Option<&PatternMatrix>,
+) -> Vec<(usize, f64)> {
+    let context = ScoreContext {
+        weights: None,
+        patterns,
+    };
+
+    HostKeeps.rank(guesses, possible, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word_list;
+
+    #[test]
+    fn rank_counts_every_answer() {
+        let answers = word_list::default_answers();
+        let possible = answers
+            .iter()
+            .step_by(5)
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let guesses = ["tares", "crane", "fuzzy", "seria"];
+
+        for (idx, kept) in rank(&guesses, &possible, None) {
+            let (_, largest) = host_reply(guesses[idx], &possible);
+            assert_eq!(kept, largest.len() as f64);
+        }
+    }
+
+    #[test]
+    fn host_keeps_the_largest_group() {
+        let answers = ["crane", "plate", "slate", "trace"];
+        let mut guesses = [Guess::parse("crane:ggggg").unwrap()];
+
+        let kept = host_replies(&mut guesses, &answers);
+        assert_eq!(kept, ["plate", "slate"]);
+        assert_eq!(
+            guesses[0].feedback,
+            Guess::parse("crane:bbgbg").unwrap().feedback
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Plays whole games against the solver to measure how good its suggestions are.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::rank::spread;
+use crate::{
+    Guess, Index, PatternMatrix, Prior, ScoreContext, Strategy, Word, feedback, pattern_index,
+};
+
+/// How many guesses needed to solve every answer.
+#[derive(Default, Debug)]
+pub struct Report {
+    pub games: Vec<(String, usize)>,
+}
+
+impl Report {
+    pub fn average(&self) -> f64 {
+        let total = self.games.iter().map(|(_, turns)| turns).sum::<usize>();
+        total as f64 / self.games.len().max(1) as f64
+    }
+
+    /// Number of games per guess count.
+    pub fn histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        for (_, turns) in &self.games {
+            *histogram.entry(*turns).or_default() += 1;
+        }
+
+        histogram
+    }
+
+    /// The `n` answers that took the most guesses, worst first.
+    pub fn worst(&self, n: usize) -> Vec<&(String, usize)> {
+        let mut games = self.games.iter().collect::<Vec<_>>();
+        games.sort_by(|(a, a_turns), (b, b_turns)| b_turns.cmp(a_turns).then_with(|| a.cmp(b)));
+        games.truncate(n);
+
+        games
+    }
+
+    /// Answers that weren't found within `max_guesses`.
+    pub fn failures(&self, max_guesses: usize) -> Vec<&str> {
+        self.games
+            .iter()
+            .filter(|(_, turns)| *turns > max_guesses)
+            .map(|(answer, _)| answer.as_str())
+            .collect()
+    }
+}
+
+/// Plays every word in `games`, always guessing the best remaining candidate from the
+/// `length` letter words of `answers` according to `strategy`, weighing candidates by `prior` and
+/// looking up `patterns` if given. Every game must be in `answers`. Of more than
+/// [`MAX_RANKED`](crate::MAX_RANKED) candidates only an evenly spaced sample is ranked.
+///
+/// The solver is deterministic, so the guess for every sequence of feedback patterns is only
+/// worked out once and shared between games.
+pub fn benchmark(
+    answers: &[String],
+    games: &[String],
+    length: usize,
+    strategy: &dyn Strategy,
+    prior: Option<&Prior>,
+    patterns: Option<&PatternMatrix>,
+) -> Report {
+    let answers = Index::new(
+        answers
+            .iter()
+            .filter(|w| w.chars().count() == length)
+            .cloned()
+            .collect(),
+    );
+    let mut next_guesses: HashMap<Vec<usize>, String> = HashMap::new();
+    let mut report = Report::default();
+
+    for answer in games {
+        let answer_chars = answer.chars().collect::<Vec<_>>();
+        let mut guesses = Vec::new();
+        let mut path = Vec::new();
+
+        loop {
+            let guess = next_guesses
+                .entry(path.clone())
+                .or_insert_with(|| {
+                    let word = Word::from_guesses(length, &guesses);
+                    let possible = answers.collect(&answers.matches(&word));
+
+                    let weights = prior.map(|prior| prior.weights(&possible));
+                    let context = ScoreContext {
+                        weights: weights.as_deref(),
+                        patterns,
+                    };
+
+                    // The answer itself is always possible, so there is a best candidate.
+                    let candidates = spread(&possible);
+                    let best = strategy.rank(&candidates, &possible, context)[0].0;
+                    candidates[best].to_string()
+                })
+                .clone();
+
+            let feedback = feedback(&guess.chars().collect::<Vec<_>>(), &answer_chars);
+            path.push(pattern_index(&feedback));
+            guesses.push(Guess {
+                word: guess.clone(),
+                feedback,
+            });
+
+            if guess == *answer {
+                break;
+            }
+        }
+
+        report.games.push((answer.clone(), guesses.len()));
+    }
+
+    report
+}
@@ -0,0 +1,177 @@
+//! Several boards played with the same guesses at once, like Dordle, Quordle and Octordle.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    Feedback, Guess, Index, PatternMatrix, ROWS, ScoreContext, Strategy, Word, distinct_letters,
+    rank::spread,
+};
+
+/// Supported numbers of boards, a single board first.
+pub const BOARD_COUNTS: [usize; 5] = [1, 2, 4, 8, 16];
+
+/// Number of guesses the game allows for `boards` boards, one more for every extra board.
+pub fn rows(boards: usize) -> usize {
+    ROWS + boards.max(1) - 1
+}
+
+// The guess that solved a board, after which the game stops coloring it.
+fn solved_at(guesses: &[Guess]) -> Option<usize> {
+    guesses.iter().position(|guess| {
+        guess.is_complete() && guess.feedback.iter().all(|&f| f == Feedback::Green)
+    })
+}
+
+/// The constraints of a single board. Guesses after the one that solved the board are ignored,
+/// the game stops coloring it.
+pub fn board_word(length: usize, guesses: &[Guess]) -> Word {
+    let solved = solved_at(guesses);
+
+    Word::from_guesses(
+        length,
+        &guesses[..solved.map_or(guesses.len(), |idx| idx + 1)],
+    )
+}
+
+/// The candidates of every board and the guesses that reveal the most across all of them, as
+/// positions in the answer list.
+#[derive(Default)]
+pub struct Boards {
+    /// Every answer that is still possible, per board.
+    pub possible: Vec<Vec<usize>>,
+    /// Candidates of the boards no guess has solved yet, best first. Answers of boards with a
+    /// single candidate come first since guessing them solves a board for sure, the rest are
+    /// ranked by the sum of their scores on the boards with more candidates.
+    pub ranked: Vec<(usize, f64)>,
+    /// Number of distinct candidates of the unsolved boards. Above
+    /// [`MAX_RANKED`](crate::MAX_RANKED) an evenly spread sample of them is ranked, along with
+    /// every known answer.
+    pub candidates: usize,
+}
+
+impl Boards {
+    /// The guesses of every board, colored for that board.
+    pub fn new(
+        answers: &Index,
+        length: usize,
+        boards: &[Vec<Guess>],
+        strategy: &dyn Strategy,
+        patterns: Option<&PatternMatrix>,
+    ) -> Self {
+        let possible = boards
+            .iter()
+            .map(|guesses| {
+                answers
+                    .matches(&board_word(length, guesses))
+                    .iter()
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let unsolved = boards
+            .iter()
+            .zip(&possible)
+            .filter(|(guesses, _)| solved_at(guesses).is_none())
+            .map(|(_, board)| board.as_slice())
+            .collect::<Vec<_>>();
+
+        // Boards with the same candidates score the same, e.g. all of them before the first guess.
+        let mut scored: BTreeMap<&[usize], usize> = BTreeMap::new();
+        for &board in unsolved.iter().filter(|board| board.len() > 1) {
+            *scored.entry(board).or_default() += 1;
+        }
+
+        let mut guesses = unsolved
+            .iter()
+            .copied()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        guesses.sort_unstable();
+        guesses.dedup();
+        let candidates = guesses.len();
+
+        let known = |idx: usize| unsolved.iter().any(|board| board == &[idx]);
+        let mut guesses = spread(&guesses);
+        if guesses.len() < candidates {
+            guesses.extend(
+                unsolved
+                    .iter()
+                    .filter(|board| board.len() == 1)
+                    .map(|board| board[0]),
+            );
+            guesses.sort_unstable();
+            guesses.dedup();
+        }
+        let guess_words = answers.words_at(&guesses);
+
+        let context = ScoreContext {
+            weights: None,
+            patterns,
+        };
+        let mut totals = vec![0.0; guesses.len()];
+        for (board, count) in scored {
+            let board = answers.words_at(board);
+            for (total, score) in
+                totals
+                    .iter_mut()
+                    .zip(strategy.scores(&guess_words, &board, context))
+            {
+                *total += score * count as f64;
+            }
+        }
+
+        let distinct = |idx: usize| distinct_letters(&answers.words()[idx]);
+        let mut ranked = guesses.into_iter().zip(totals).collect::<Vec<_>>();
+        ranked.sort_by(|&(a, a_score), &(b, b_score)| {
+            known(b)
+                .cmp(&known(a))
+                .then_with(|| strategy.compare(a_score, b_score))
+                .then_with(|| distinct(b).cmp(&distinct(a)))
+        });
+
+        Boards {
+            possible,
+            ranked,
+            candidates,
+        }
+    }
+
+    /// Whether only one candidate is left on `board`.
+    pub fn is_solved(&self, board: usize) -> bool {
+        self.possible[board].len() == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Entropy;
+
+    #[test]
+    fn known_answers_come_first() {
+        let answers = Index::new(
+            ["crane", "crate", "grate", "plate", "slate"]
+                .map(String::from)
+                .to_vec(),
+        );
+        let guess = |colors: &str| vec![Guess::parse(&format!("crane:{colors}")).unwrap()];
+        // The first board is solved, the second is down to "crate", the last two aren't.
+        let boards = [
+            guess("ggggg"),
+            guess("gggbg"),
+            guess("bbgbg"),
+            guess("bbgbg"),
+        ];
+
+        let all = Boards::new(&answers, 5, &boards, &Entropy, None);
+        let ranked = answers.words_at(&all.ranked.iter().map(|&(w, _)| w).collect::<Vec<_>>());
+        assert_eq!(ranked[0], "crate");
+        assert_eq!(ranked.len(), 3);
+        assert!(!ranked.contains(&"crane"));
+
+        // With every board down to one candidate there is still something to guess.
+        let boards = [guess("ggggg"), guess("gggbg")];
+        let all = Boards::new(&answers, 5, &boards, &Entropy, None);
+        assert_eq!(answers.words_at(&[all.ranked[0].0]), ["crate"]);
+    }
+}
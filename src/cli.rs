@@ -0,0 +1,596 @@
+use std::ops::ControlFlow;
+use std::path::Path;
+
+use wordle_helper::word_list::Accents;
+use wordle_helper::{
+    DEFAULT_LENGTH, Feedback, Guess, Index, MAX_LENGTH, MIN_LENGTH, PatternMatrix, Prior, ROWS,
+    STRATEGIES, Scored, Strategy, Suggestions, Tree, Word, WorstCase, absurdle, bench, fibble,
+    lookahead, nerdle, strategy, word_list,
+};
+
+pub const USAGE: &str = "\
+Usage: wordle-helper [COMMAND] [OPTIONS]
+
+Starts the GUI if no command is given.
+
+Commands:
+  solve     Print the candidates and best guesses for the given feedback
+  bench     Play every answer against the solver and report how many guesses it needs
+  tree      Work out what to guess for every possible feedback and save it to a file
+  absurdle  Play words against an Absurdle host and print how it answers them
+
+Options:
+  --list <PATH>         Answer list, defaults to the built-in list
+  --allowed <PATH>      Additional words that may be guessed but are never the answer
+                        (solve, tree, absurdle)
+  --frequencies <PATH>  Word frequencies, one word and its count per line. Common words are
+                        considered more likely answers (solve, bench)
+  --guess <WORD:COLORS> A guess and its feedback, g = green, y = yellow, b = grey.
+                        May be repeated, e.g. --guess crane:gybbb (solve)
+  --word <WORD>         A word played against the host, may be repeated (absurdle)
+  --length <N>          Word length if no guess is given [default: 5]
+  --hard                Only suggest probes that are valid in hard mode (solve)
+  --lookahead           Also search two guesses deep if 3 to 30 candidates are left (solve)
+  --strategy <NAME>     entropy, minimax, expected, frequency, positional or distinct
+                        [default: entropy]. bench may repeat it and compares all by default
+  --nerdle              Guess equations like 12+35=47 instead of words, every valid equation
+                        of the length is a candidate. Equations are 6, 8 or 10 characters
+                        long [default length: 8] (solve, bench)
+  --lies <N>            Play Fibble: every guess has exactly N tiles colored wrong. Guesses
+                        are ranked by the entropy of the colors the game may show (solve)
+  --sample <N>          Only play N evenly spaced answers (bench)
+  --opening <WORD>      First guess of the tree, its length sets the word length (tree)
+  --out <PATH>          Where to save the tree, as JSON if it ends in .json and in a
+                        compact binary format otherwise (tree)
+  --cache <DIR>         Precompute the feedback of every guess against every answer and keep
+                        it in DIR, so it is only computed once per word list. Takes about
+                        220 MB for the built-in list, larger lists are played without it
+                        (solve, bench, absurdle)
+  --top <N>             Number of suggestions or worst cases to print [default: 10]
+  --accents <MODE>      keep treats accented letters like ñ as letters of their own, strip
+                        turns them into the plain letter [default: keep]. With keep, words
+                        with a letter and mark that have no single character, like the
+                        Devanagari क्, are rejected since a tile holds one character";
+
+#[derive(Default)]
+struct Args {
+    list: Option<String>,
+    allowed: Option<String>,
+    frequencies: Option<String>,
+    guesses: Vec<Guess>,
+    words: Vec<String>,
+    length: Option<usize>,
+    hard_mode: bool,
+    lookahead: bool,
+    lies: Option<usize>,
+    nerdle: bool,
+    strategies: Vec<&'static dyn Strategy>,
+    sample: Option<usize>,
+    opening: Option<String>,
+    out: Option<String>,
+    cache: Option<String>,
+    top: Option<usize>,
+    accents: Accents,
+}
+
+fn parse_number(flag: &str, value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{flag} expects a number, got \"{value}\""))
+}
+
+fn parse_accents(name: &str) -> Result<Accents, String> {
+    Accents::parse(name).ok_or_else(|| format!("--accents expects keep or strip, got \"{name}\""))
+}
+
+fn parse_strategy(name: &str) -> Result<&'static dyn Strategy, String> {
+    strategy(name).ok_or_else(|| format!("unknown strategy \"{name}\""))
+}
+
+// Only the flags in `accepted` are allowed, so options of other commands aren't silently ignored.
+fn parse_args(args: &[String], accepted: &[&str]) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if !accepted.contains(&arg.as_str()) {
+            return Err(format!("unexpected argument \"{arg}\"\n\n{USAGE}"));
+        }
+
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("{arg} expects a value"))
+        };
+
+        match arg.as_str() {
+            "--list" => parsed.list = Some(value()?),
+            "--allowed" => parsed.allowed = Some(value()?),
+            "--frequencies" => parsed.frequencies = Some(value()?),
+            "--guess" => parsed.guesses.push(Guess::parse(&value()?)?),
+            "--length" => parsed.length = Some(parse_number(arg, &value()?)?),
+            "--lies" => parsed.lies = Some(parse_number(arg, &value()?)?),
+            "--strategy" => parsed.strategies.push(parse_strategy(&value()?)?),
+            "--sample" => parsed.sample = Some(parse_number(arg, &value()?)?),
+            "--opening" => parsed.opening = Some(value()?),
+            "--out" => parsed.out = Some(value()?),
+            "--cache" => parsed.cache = Some(value()?),
+            "--top" => parsed.top = Some(parse_number(arg, &value()?)?),
+            "--word" => parsed.words.push(value()?),
+            "--accents" => parsed.accents = parse_accents(&value()?)?,
+            "--hard" => parsed.hard_mode = true,
+            "--lookahead" => parsed.lookahead = true,
+            "--nerdle" => parsed.nerdle = true,
+            _ => unreachable!("accepted flag {arg} isn't handled"),
+        }
+    }
+
+    // Typed words are folded like the lists they are compared with.
+    for guess in &mut parsed.guesses {
+        guess.word = word_list::fold(&guess.word, parsed.accents);
+    }
+    for w in parsed.words.iter_mut().chain(&mut parsed.opening) {
+        *w = word_list::fold(w, parsed.accents);
+    }
+
+    Ok(parsed)
+}
+
+fn read_list(path: &str, accents: Accents) -> Result<Vec<String>, String> {
+    let (list, report) = word_list::read(Path::new(path), accents)
+        .ok_or_else(|| format!("can't read \"{path}\""))?;
+    eprintln!("{path}: {}", report.summary().replace('\n', "; "));
+
+    Ok(list)
+}
+
+fn check_length(length: usize) -> Result<(), String> {
+    if !(MIN_LENGTH..=MAX_LENGTH).contains(&length) {
+        return Err(format!(
+            "words must be {MIN_LENGTH} to {MAX_LENGTH} letters long"
+        ));
+    }
+
+    Ok(())
+}
+
+fn default_length(args: &Args) -> usize {
+    match args.length {
+        Some(length) => length,
+        None if args.nerdle => nerdle::DEFAULT_LENGTH,
+        None => DEFAULT_LENGTH,
+    }
+}
+
+// Nerdle generates the equations of the one length that is played.
+fn read_answers(args: &Args, length: usize) -> Result<Vec<String>, String> {
+    match &args.list {
+        Some(_) if args.nerdle => Err("--nerdle can't be combined with --list".to_string()),
+        Some(path) => read_list(path, args.accents),
+        None if args.nerdle && !nerdle::LENGTHS.contains(&length) => Err(format!(
+            "Nerdle equations are one of {:?} characters long",
+            nerdle::LENGTHS
+        )),
+        None if args.nerdle => Ok(nerdle::equations(length)),
+        None => Ok(word_list::default_answers()),
+    }
+}
+
+fn read_prior(args: &Args) -> Result<Option<Prior>, String> {
+    let Some(path) = &args.frequencies else {
+        return Ok(None);
+    };
+
+    let prior =
+        Prior::read(Path::new(path), args.accents).map_err(|err| format!("{path}: {err}"))?;
+    eprintln!("{path}: loaded {} frequencies", prior.len());
+
+    Ok(Some(prior))
+}
+
+// Patterns are only precomputed when asked for with --cache and a strategy looks them up.
+fn read_patterns(
+    dir: Option<&str>,
+    uses_patterns: bool,
+    guesses: &[&str],
+    answers: &[&str],
+) -> Option<PatternMatrix> {
+    let dir = dir.filter(|_| uses_patterns)?;
+    let Some((matrix, written)) = PatternMatrix::cached(guesses, answers, Path::new(dir)) else {
+        eprintln!(
+            "not caching patterns, they would take {} MB instead of at most {} MB",
+            PatternMatrix::bytes(guesses, answers) >> 20,
+            PatternMatrix::MAX_BYTES >> 20
+        );
+        return None;
+    };
+    if let Err(err) = written {
+        eprintln!("failed to cache patterns in {err}");
+    }
+
+    Some(matrix)
+}
+
+// Candidates also show how likely they are to be the answer. `words` is the list the guesses
+// were ranked from.
+fn print_ranked(title: &str, ranked: &[Scored], words: &Index, top: usize, probability: bool) {
+    println!("{title}:");
+    for scored in ranked.iter().take(top) {
+        let mut notes = Vec::new();
+        match scored.worst_case {
+            Some(WorstCase::Exact(count)) => notes.push(format!("at most {count} left")),
+            Some(WorstCase::Estimate(count)) => notes.push(format!("at most ~{count} left")),
+            None => {}
+        }
+        if probability {
+            notes.push(format!("{:.1}% likely", scored.probability * 100.0));
+        }
+
+        let notes = if notes.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", notes.join(", "))
+        };
+        println!(
+            "  {} {:.2}{notes}",
+            words.words()[scored.word],
+            scored.score
+        );
+    }
+}
+
+pub fn solve(args: &[String]) -> Result<(), String> {
+    let args = parse_args(
+        args,
+        &[
+            "--list",
+            "--accents",
+            "--allowed",
+            "--frequencies",
+            "--guess",
+            "--length",
+            "--hard",
+            "--lookahead",
+            "--strategy",
+            "--lies",
+            "--nerdle",
+            "--cache",
+            "--top",
+        ],
+    )?;
+    if args.lies.is_some() && (args.hard_mode || args.lookahead || !args.strategies.is_empty()) {
+        return Err("--lies can't be combined with --hard, --lookahead or --strategy".to_string());
+    }
+
+    let length = match args.guesses.first() {
+        Some(guess) => guess.feedback.len(),
+        None => default_length(&args),
+    };
+    if let Some(wanted) = args.length
+        && wanted != length
+    {
+        return Err(format!(
+            "--length is {wanted} but the guesses are {length} letters long"
+        ));
+    }
+    check_length(length)?;
+    if args
+        .guesses
+        .iter()
+        .any(|guess| guess.feedback.len() != length)
+    {
+        return Err("all guesses must have the same length".to_string());
+    }
+    for guess in args.guesses.iter().filter(|_| args.nerdle) {
+        if !guess.word.chars().all(|c| nerdle::ALPHABET.contains(c)) {
+            return Err(format!(
+                "\"{}\": equations only use {}",
+                guess.word,
+                nerdle::ALPHABET
+            ));
+        }
+        if !nerdle::is_valid(&guess.word) {
+            return Err(format!("\"{}\" isn't a valid equation", guess.word));
+        }
+    }
+    if args.lies.is_some_and(|lies| lies > length) {
+        return Err(format!(
+            "--lies can't be more than the {length} tiles of a row"
+        ));
+    }
+
+    let answers = read_answers(&args, length)?;
+    let guess_list = match &args.allowed {
+        Some(path) => read_list(path, args.accents)?,
+        None => Vec::new(),
+    };
+    let allowed = word_list::merge(&answers, &guess_list);
+
+    if args.strategies.len() > 1 {
+        return Err("solve uses a single strategy".to_string());
+    }
+    let lying = args.lies.map(|lies| fibble::LyingEntropy { lies });
+    let strategy: &dyn Strategy = match &lying {
+        Some(lying) => lying,
+        None => args.strategies.first().copied().unwrap_or(STRATEGIES[0]),
+    };
+    let prior = read_prior(&args)?;
+    let answers = Index::new(answers);
+    let allowed = Index::new(allowed);
+    let patterns = read_patterns(
+        args.cache.as_deref(),
+        strategy.uses_patterns(),
+        &allowed.collect(&allowed.of_length(length)),
+        &answers.collect(&answers.of_length(length)),
+    );
+    let suggestions = match args.lies {
+        Some(lies) => Suggestions::from_possible(
+            &answers,
+            fibble::possible(&answers, length, &args.guesses, lies),
+            &allowed,
+            &allowed.of_length(length).iter().collect::<Vec<_>>(),
+            strategy,
+            prior.as_ref(),
+            patterns.as_ref(),
+        ),
+        None => Suggestions::new(
+            &answers,
+            &allowed,
+            &Word::from_guesses(length, &args.guesses),
+            args.hard_mode,
+            strategy,
+            prior.as_ref(),
+            patterns.as_ref(),
+        ),
+    };
+    let top = args.top.unwrap_or(10);
+
+    println!("{} possible words", suggestions.possible.len());
+    print_ranked("Candidates", &suggestions.ranked, &answers, top, true);
+    print_ranked("Best probes", &suggestions.probes, &allowed, top, false);
+    if let Some(probe) = suggestions.better_probe {
+        let probe = &allowed.words()[probe];
+        println!("\"{probe}\" can't be the answer but beats every candidate");
+    }
+
+    if args.lookahead && lookahead::is_endgame(suggestions.possible.len()) {
+        let pool = lookahead::pool(&suggestions, &answers, &allowed);
+        let possible = answers.words_at(&suggestions.possible);
+        let expected =
+            lookahead::expected_guesses(&pool, &possible, |_, _| ControlFlow::Continue(()));
+
+        println!("Lookahead, expected guesses to finish:");
+        for (w, expected) in expected.unwrap_or_default().iter().take(top) {
+            println!("  {w} {expected:.3}");
+        }
+    }
+
+    Ok(())
+}
+
+pub fn bench(args: &[String]) -> Result<(), String> {
+    let mut args = parse_args(
+        args,
+        &[
+            "--list",
+            "--accents",
+            "--frequencies",
+            "--length",
+            "--nerdle",
+            "--strategy",
+            "--sample",
+            "--cache",
+            "--top",
+        ],
+    )?;
+
+    let length = default_length(&args);
+    check_length(length)?;
+
+    let mut answers = read_answers(&args, length)?;
+    let prior = read_prior(&args)?;
+    answers.retain(|w| w.chars().count() == length);
+    let games = match args.sample {
+        Some(sample) if sample < answers.len() => (0..sample)
+            .map(|idx| answers[idx * answers.len() / sample].clone())
+            .collect(),
+        _ => answers.clone(),
+    };
+
+    if args.strategies.is_empty() {
+        args.strategies = STRATEGIES.to_vec();
+    }
+    let top = args.top.unwrap_or(10);
+    let words = answers.iter().map(String::as_str).collect::<Vec<_>>();
+    let patterns = read_patterns(
+        args.cache.as_deref(),
+        args.strategies
+            .iter()
+            .any(|strategy| strategy.uses_patterns()),
+        &words,
+        &words,
+    );
+
+    for strategy in args.strategies {
+        let report = bench::benchmark(
+            &answers,
+            &games,
+            length,
+            strategy,
+            prior.as_ref(),
+            patterns.as_ref(),
+        );
+        let failures = report.failures(ROWS);
+
+        println!(
+            "{}: {:.3} guesses on average over {} answers, {} failures",
+            strategy.name(),
+            report.average(),
+            report.games.len(),
+            failures.len()
+        );
+        for (turns, games) in report.histogram() {
+            println!("  {turns:>2} guesses: {games}");
+        }
+
+        let worst = report
+            .worst(top)
+            .into_iter()
+            .map(|(answer, turns)| format!("{answer} ({turns})"))
+            .collect::<Vec<_>>();
+        println!("  worst: {}", worst.join(", "));
+        if !failures.is_empty() {
+            println!("  failed: {}", failures.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn tree(args: &[String]) -> Result<(), String> {
+    let args = parse_args(
+        args,
+        &[
+            "--list",
+            "--accents",
+            "--allowed",
+            "--strategy",
+            "--opening",
+            "--out",
+        ],
+    )?;
+
+    let opening = args.opening.as_deref().ok_or("tree needs --opening")?;
+    let out = args.out.as_deref().ok_or("tree needs --out")?;
+    check_length(opening.chars().count())?;
+    if args.strategies.len() > 1 {
+        return Err("tree uses a single strategy".to_string());
+    }
+    let strategy = args.strategies.first().copied().unwrap_or(STRATEGIES[0]);
+
+    let length = opening.chars().count();
+    let answers = read_answers(&args, length)?;
+    // Without --allowed only candidates are guessed, like the benchmark plays.
+    let allowed = match &args.allowed {
+        Some(path) => word_list::merge(&answers, &read_list(path, args.accents)?),
+        None => Vec::new(),
+    };
+    let tree = Tree::build(&answers, &allowed, opening, strategy);
+    tree.save(Path::new(out))
+        .map_err(|err| format!("can't write \"{out}\": {err}"))?;
+
+    println!(
+        "{} guesses, every answer found within {}",
+        tree.size(),
+        tree.depth()
+    );
+    let answers = Index::new(answers);
+    let too_slow = answers
+        .collect(&answers.of_length(length))
+        .into_iter()
+        .filter(|answer| {
+            tree.guesses_for(answer)
+                .is_none_or(|guesses| guesses > ROWS)
+        })
+        .count();
+    if too_slow > 0 {
+        eprintln!("{too_slow} answers need more than the {ROWS} guesses the game allows");
+    }
+
+    Ok(())
+}
+
+fn colors(feedback: &[Feedback]) -> String {
+    feedback
+        .iter()
+        .map(|feedback| match feedback {
+            Feedback::Green => 'g',
+            Feedback::Yellow => 'y',
+            Feedback::Grey => 'b',
+        })
+        .collect()
+}
+
+pub fn absurdle(args: &[String]) -> Result<(), String> {
+    let args = parse_args(
+        args,
+        &[
+            "--list",
+            "--accents",
+            "--allowed",
+            "--word",
+            "--length",
+            "--cache",
+            "--top",
+        ],
+    )?;
+
+    let length = match args.words.first() {
+        Some(w) => w.chars().count(),
+        None => args.length.unwrap_or(DEFAULT_LENGTH),
+    };
+    check_length(length)?;
+    if args.words.iter().any(|w| w.chars().count() != length) {
+        return Err("all words must have the same length".to_string());
+    }
+
+    let mut answers = read_answers(&args, length)?;
+    let guess_list = match &args.allowed {
+        Some(path) => read_list(path, args.accents)?,
+        None => Vec::new(),
+    };
+    let mut allowed = word_list::merge(&answers, &guess_list);
+    answers.retain(|w| w.chars().count() == length);
+    allowed.retain(|w| w.chars().count() == length);
+
+    let answers = answers.iter().map(String::as_str).collect::<Vec<_>>();
+    let allowed = allowed.iter().map(String::as_str).collect::<Vec<_>>();
+    let mut possible = answers.clone();
+    for w in &args.words {
+        let (feedback, kept) = absurdle::host_reply(w, &possible);
+        println!(
+            "{w}: {}, the host keeps {} of {}",
+            colors(&feedback),
+            kept.len(),
+            possible.len()
+        );
+        possible = kept;
+    }
+
+    let top = args.top.unwrap_or(10);
+    println!("{} possible words", possible.len());
+    println!("Best guesses:");
+    let patterns = read_patterns(
+        args.cache.as_deref(),
+        absurdle::HostKeeps.uses_patterns(),
+        &allowed,
+        &answers,
+    );
+    let ranked = absurdle::rank(&allowed, &possible, patterns.as_ref());
+    for &(idx, kept) in ranked.iter().take(top) {
+        println!("  {} (the host keeps at most {kept:.0})", allowed[idx]);
+    }
+
+    Ok(())
+}
+
+// Runs a subcommand if one was given, returns `None` to start the GUI instead.
+pub fn run(args: &[String]) -> Option<Result<(), String>> {
+    let (command, args) = args.split_first()?;
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return Some(Ok(()));
+    }
+
+    Some(match command.as_str() {
+        "solve" => solve(args),
+        "bench" => bench(args),
+        "tree" => tree(args),
+        "absurdle" => absurdle(args),
+        "-h" | "--help" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        _ => Err(format!("unknown command \"{command}\"\n\n{USAGE}")),
+    })
+}
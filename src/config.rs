@@ -0,0 +1,96 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use wordle_helper::word_list::Accents;
+use wordle_helper::{DEFAULT_LENGTH, Guess, STRATEGIES};
+
+// Everything restored on the next launch.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct Config {
+    pub answer_list: Option<PathBuf>,
+    pub guess_list: Option<PathBuf>,
+    pub frequencies: Option<PathBuf>,
+    pub tree: Option<PathBuf>,
+    pub length: usize,
+    pub hard_mode: bool,
+    pub strategy: String,
+    pub lookahead: bool,
+    // Precompute the feedback patterns for the strategies that look them up.
+    pub cache_patterns: bool,
+    pub absurdle: bool,
+    // Tiles Fibble colors wrong per guess, 0 when playing Wordle.
+    pub lies: usize,
+    // Equations instead of words, `length` is then the length of the equation.
+    pub nerdle: bool,
+    pub accents: Accents,
+    pub boards: usize,
+    // The first board, the others follow in `other_boards`.
+    pub guesses: Vec<Guess>,
+    pub other_boards: Vec<Vec<Guess>>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            answer_list: None,
+            guess_list: None,
+            frequencies: None,
+            tree: None,
+            length: DEFAULT_LENGTH,
+            hard_mode: false,
+            strategy: STRATEGIES[0].name().to_string(),
+            lookahead: false,
+            cache_patterns: false,
+            absurdle: false,
+            lies: 0,
+            nerdle: false,
+            accents: Accents::Keep,
+            boards: 1,
+            guesses: Vec::new(),
+            other_boards: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    fn path() -> Option<PathBuf> {
+        Some(
+            dirs::config_dir()?
+                .join("wordle-helper")
+                .join("config.json"),
+        )
+    }
+
+    // A missing or unreadable config file just means starting fresh.
+    pub fn load() -> Self {
+        Config::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|config| serde_json::from_str(&config).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = Config::path() else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn forget() -> std::io::Result<()> {
+        let Some(path) = Config::path() else {
+            return Ok(());
+        };
+
+        match fs::remove_file(path) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+
+use crate::word_list::{Accents, fold};
+
+/// The color the game gives a single tile.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum Feedback {
+    /// The letter is not in the answer, or not as often as it was guessed.
+    #[default]
+    Grey,
+    /// The letter is in the answer, but somewhere else.
+    Yellow,
+    /// The letter is in the answer at this position.
+    Green,
+}
+
+impl Feedback {
+    /// Cycles grey → yellow → green → grey, the way clicking a tile does.
+    pub fn next(self) -> Self {
+        match self {
+            Feedback::Grey => Feedback::Yellow,
+            Feedback::Yellow => Feedback::Green,
+            Feedback::Green => Feedback::Grey,
+        }
+    }
+
+    /// Parses `g`, `y` or `b` (for black) in either case.
+    pub fn from_char(ch: char) -> Option<Self> {
+        match ch.to_ascii_lowercase() {
+            'g' => Some(Feedback::Green),
+            'y' => Some(Feedback::Yellow),
+            'b' => Some(Feedback::Grey),
+            _ => None,
+        }
+    }
+}
+
+/// A guessed word together with the feedback the game gave for it.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Guess {
+    pub word: String,
+    pub feedback: Vec<Feedback>,
+}
+
+impl Guess {
+    /// An empty guess for words of `length` letters.
+    pub fn new(length: usize) -> Self {
+        Guess {
+            word: String::new(),
+            feedback: vec![Feedback::Grey; length],
+        }
+    }
+
+    /// Parses `WORD:COLORS`, e.g. `crane:gybbb`, with one [`Feedback::from_char`] color per
+    /// letter.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (word, colors) = s
+            .split_once(':')
+            .ok_or_else(|| format!("guess \"{s}\" must look like WORD:COLORS"))?;
+        let word = fold(word, Accents::Keep);
+
+        let feedback = colors
+            .chars()
+            .map(|ch| {
+                Feedback::from_char(ch)
+                    .ok_or_else(|| format!("unknown color '{ch}' in \"{s}\", use g, y or b"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if word.chars().count() != feedback.len() {
+            return Err(format!("\"{s}\" needs one color per letter"));
+        }
+
+        Ok(Guess { word, feedback })
+    }
+
+    /// Whether a letter has been entered for every tile.
+    pub fn is_complete(&self) -> bool {
+        self.word.chars().count() == self.feedback.len()
+    }
+}
+
+/// Colors `guess` the way the game would if `answer` was the solution.
+///
+/// Repeated letters are handled like the game does: greens are assigned first, then every
+/// remaining letter of the answer can turn at most one guessed letter yellow.
+pub fn feedback(guess: &[char], answer: &[char]) -> Vec<Feedback> {
+    let mut feedback = vec![Feedback::Grey; guess.len()];
+    let mut unused = Vec::with_capacity(answer.len());
+
+    for (idx, (g, a)) in guess.iter().zip(answer).enumerate() {
+        if g == a {
+            feedback[idx] = Feedback::Green;
+        } else {
+            unused.push(*a);
+        }
+    }
+
+    for (idx, g) in guess.iter().enumerate() {
+        if feedback[idx] == Feedback::Green {
+            continue;
+        }
+
+        if let Some(pos) = unused.iter().position(|a| a == g) {
+            feedback[idx] = Feedback::Yellow;
+            unused.swap_remove(pos);
+        }
+    }
+
+    feedback
+}
+
+/// The [`pattern_index`] of the [`feedback`] `guess` gets against `answer`, without allocating.
+/// Meant for inner loops over whole word lists.
+pub fn pattern(guess: &[char], answer: &[char]) -> usize {
+    // Longer than any word or equation that is played.
+    const MAX: usize = 16;
+    if guess.len() > MAX {
+        return pattern_index(&feedback(guess, answer));
+    }
+
+    let mut colors = [Feedback::Grey; MAX];
+    let mut unused = ['\0'; MAX];
+    let mut unused_len = 0;
+    for (idx, (g, a)) in guess.iter().zip(answer).enumerate() {
+        if g == a {
+            colors[idx] = Feedback::Green;
+        } else {
+            unused[unused_len] = *a;
+            unused_len += 1;
+        }
+    }
+
+    for (idx, g) in guess.iter().enumerate() {
+        if colors[idx] == Feedback::Green {
+            continue;
+        }
+
+        if let Some(pos) = unused[..unused_len].iter().position(|a| a == g) {
+            colors[idx] = Feedback::Yellow;
+            unused_len -= 1;
+            unused.swap(pos, unused_len);
+        }
+    }
+
+    pattern_index(&colors[..guess.len()])
+}
+
+/// Encodes a feedback pattern as a base 3 number, unique for patterns of the same length.
+pub fn pattern_index(feedback: &[Feedback]) -> usize {
+    feedback
+        .iter()
+        .rev()
+        .fold(0, |acc, &feedback| acc * 3 + feedback as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colors(guess: &str, answer: &str) -> String {
+        let guess = guess.chars().collect::<Vec<_>>();
+        let answer = answer.chars().collect::<Vec<_>>();
+        feedback(&guess, &answer)
+            .into_iter()
+            .map(|feedback| match feedback {
+                Feedback::Grey => 'b',
+                Feedback::Yellow => 'y',
+                Feedback::Green => 'g',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn repeated_letters() {
+        // Only one of the two guessed E's can match the single E in the answer.
+        assert_eq!(colors("speed", "abide"), "bbyby");
+        // Greens are assigned before yellows.
+        assert_eq!(colors("geese", "these"), "bbggg");
+        assert_eq!(colors("eerie", "melee"), "ygbbg");
+        assert_eq!(colors("llama", "hello"), "yybbb");
+    }
+
+    #[test]
+    fn pattern_matches_feedback() {
+        let words = [
+            "speed", "abide", "geese", "these", "eerie", "melee", "llama", "hello",
+        ];
+        for guess in words {
+            for answer in words {
+                let guess = guess.chars().collect::<Vec<_>>();
+                let answer = answer.chars().collect::<Vec<_>>();
+                assert_eq!(
+                    pattern(&guess, &answer),
+                    pattern_index(&feedback(&guess, &answer))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pattern_index_is_base_3() {
+        let green = pattern_index(&[Feedback::Green; 5]);
+        assert_eq!(green, 3usize.pow(5) - 1);
+        assert_eq!(pattern_index(&[Feedback::Yellow, Feedback::Grey]), 1);
+        assert_eq!(pattern_index(&[Feedback::Grey, Feedback::Yellow]), 3);
+    }
+
+    #[test]
+    fn parse_checks_colors() {
+        let guess = Guess::parse("crane:GYbbb").unwrap();
+        assert_eq!(guess.word, "crane");
+        assert_eq!(guess.feedback[..2], [Feedback::Green, Feedback::Yellow]);
+        assert!(Guess::parse("crane:gyb").is_err());
+        assert!(Guess::parse("crane:gybbx").is_err());
+    }
+}
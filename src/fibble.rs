@@ -0,0 +1,198 @@
+//! Fibble, where the game lies about a fixed number of tiles in every row.
+
+use crate::prior::normalized;
+use crate::{Feedback, Guess, Index, ScoreContext, Strategy, WorstCase, feedback, pattern_index};
+
+// Every answer shows up as several lied patterns, so fewer of them are sampled than usual.
+const SAMPLE_SIZE: usize = 300;
+
+/// Number of tiles that differ between two feedbacks of the same length.
+pub fn differences(a: &[Feedback], b: &[Feedback]) -> usize {
+    a.iter().zip(b).filter(|(a, b)| a != b).count()
+}
+
+/// Whether `answer` could be the answer if every complete guess has exactly `lies` tiles
+/// colored wrong.
+pub fn allows(guesses: &[Guess], answer: &str, lies: usize) -> bool {
+    let answer = answer.chars().collect::<Vec<_>>();
+
+    guesses
+        .iter()
+        .filter(|guess| guess.is_complete())
+        .all(|guess| {
+            let guess_chars = guess.word.chars().collect::<Vec<_>>();
+            guess_chars.len() == answer.len()
+                && differences(&feedback(&guess_chars, &answer), &guess.feedback) == lies
+        })
+}
+
+/// Positions of the `length` letter `answers` that [`allows`] keeps, ascending.
+pub fn possible(answers: &Index, length: usize, guesses: &[Guess], lies: usize) -> Vec<usize> {
+    answers
+        .of_length(length)
+        .iter()
+        .filter(|&idx| allows(guesses, &answers.words()[idx], lies))
+        .collect()
+}
+
+/// Calls `shown` with the [`pattern_index`] of every feedback the game can show for `truth`
+/// with exactly `lies` tiles changed to another color.
+pub fn lied_patterns(truth: &[Feedback], lies: usize, mut shown: impl FnMut(usize)) {
+    let place_values = truth
+        .iter()
+        .scan(1, |value, _| {
+            let place = *value;
+            *value *= 3;
+            Some(place)
+        })
+        .collect::<Vec<_>>();
+
+    lie_from(
+        truth,
+        &place_values,
+        0,
+        lies,
+        pattern_index(truth),
+        &mut shown,
+    );
+}
+
+fn lie_from(
+    truth: &[Feedback],
+    place_values: &[usize],
+    from: usize,
+    lies: usize,
+    pattern: usize,
+    shown: &mut impl FnMut(usize),
+) {
+    if lies == 0 {
+        shown(pattern);
+        return;
+    }
+
+    for pos in from..truth.len() {
+        let color = truth[pos] as usize;
+        for other in (0..3).filter(|&other| other != color) {
+            let pattern = pattern - color * place_values[pos] + other * place_values[pos];
+            lie_from(truth, place_values, pos + 1, lies - 1, pattern, shown);
+        }
+    }
+}
+
+/// Shannon entropy in bits of the feedback Fibble shows, with every way of placing `lies` lies
+/// in a row equally likely. Lies blur the groups a guess splits the candidates into, so this
+/// prefers different guesses than plain [`Entropy`](crate::Entropy).
+pub struct LyingEntropy {
+    pub lies: usize,
+}
+
+impl Strategy for LyingEntropy {
+    fn name(&self) -> &'static str {
+        "fibble"
+    }
+
+    fn label(&self) -> &'static str {
+        "Entropy with lies (bits)"
+    }
+
+    fn scores(&self, guesses: &[&str], possible: &[&str], context: ScoreContext) -> Vec<f64> {
+        self.scores_with_worst_cases(guesses, possible, context)
+            .into_iter()
+            .map(|(score, _)| score)
+            .collect()
+    }
+
+    // The worst case is the most candidates that can show the same lied pattern.
+    fn scores_with_worst_cases(
+        &self,
+        guesses: &[&str],
+        possible: &[&str],
+        context: ScoreContext,
+    ) -> Vec<(f64, Option<WorstCase>)> {
+        let step = possible.len().div_ceil(SAMPLE_SIZE).max(1);
+        let answers = possible
+            .iter()
+            .step_by(step)
+            .map(|w| w.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let weights = normalized(match context.weights {
+            Some(weights) => weights.iter().step_by(step).copied().collect(),
+            None => vec![1.0; answers.len()],
+        });
+        let scale = possible.len() as f64 / answers.len().max(1) as f64;
+
+        // Probability and number of candidates of every shown pattern, and the patterns that
+        // have any.
+        let length = answers.first().map_or(0, Vec::len);
+        let mut groups = vec![(0.0, 0); 3usize.pow(length as u32)];
+        let mut seen = Vec::new();
+        let mut shown = Vec::new();
+
+        guesses
+            .iter()
+            .map(|guess| {
+                let guess = guess.chars().collect::<Vec<_>>();
+                for (answer, &weight) in answers.iter().zip(&weights) {
+                    shown.clear();
+                    lied_patterns(&feedback(&guess, answer), self.lies, |p| shown.push(p));
+
+                    let share = weight / shown.len().max(1) as f64;
+                    for &pattern in &shown {
+                        let (mass, count) = &mut groups[pattern];
+                        if *count == 0 {
+                            seen.push(pattern);
+                        }
+                        *mass += share;
+                        *count += 1;
+                    }
+                }
+
+                let mut largest = 0;
+                let entropy = seen
+                    .drain(..)
+                    .map(|pattern| {
+                        let (p, count) = std::mem::take(&mut groups[pattern]);
+                        largest = largest.max(count);
+                        if p > 0.0 { -p * p.log2() } else { 0.0 }
+                    })
+                    .sum();
+                let worst_case = if scale > 1.0 {
+                    WorstCase::Estimate((largest as f64 * scale).round() as usize)
+                } else {
+                    WorstCase::Exact(largest)
+                };
+
+                (entropy, Some(worst_case))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_lie_changes_one_tile() {
+        let truth = [Feedback::Green, Feedback::Grey, Feedback::Yellow];
+        let mut shown = Vec::new();
+        lied_patterns(&truth, 1, |p| shown.push(p));
+
+        assert_eq!(shown.len(), 6);
+        assert!(!shown.contains(&pattern_index(&truth)));
+    }
+
+    #[test]
+    fn worst_case_counts_lied_patterns() {
+        let possible = ["crane", "crate", "slate"];
+        let scored = LyingEntropy { lies: 1 }.scores_with_worst_cases(
+            &["crane"],
+            &possible,
+            ScoreContext::default(),
+        );
+
+        // The true patterns are ggggg, gggbg and bbgbg. No pattern is one lie from all three,
+        // but gggyg is one lie from the first two.
+        assert_eq!(scored[0].1, Some(WorstCase::Exact(2)));
+    }
+}
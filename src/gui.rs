@@ -0,0 +1,1172 @@
+use std::iter;
+use std::ops::ControlFlow;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use eframe::egui::{
+    Button, CentralPanel, Checkbox, Color32, ComboBox, Context, DragValue, FontId, ProgressBar,
+    RichText, ScrollArea, TextEdit, TextStyle, Ui, Vec2, ViewportBuilder, ViewportCommand,
+};
+use wordle_helper::boards::{BOARD_COUNTS, Boards, board_word, rows};
+use wordle_helper::tree::Next;
+use wordle_helper::word_list::{self, Accents, LoadReport};
+use wordle_helper::{
+    DEFAULT_LENGTH, Feedback, Guess, Index, MAX_LENGTH, MAX_RANKED, MIN_LENGTH, PatternMatrix,
+    Prior, ROWS, STRATEGIES, Scored, Strategy, Suggestions, Tree, WorstCase, absurdle, fibble,
+    guess_warnings, lookahead, nerdle, strategy,
+};
+
+use crate::config::Config;
+
+const WINDOW_HEIGHT: f32 = 700.0;
+const FIELD_SIZE: Vec2 = Vec2 { x: 70.0, y: 30.0 };
+const TILE_SIZE: Vec2 = Vec2 { x: 30.0, y: 30.0 };
+const SCORE_HINT: &str = "Word, strategy score and the most words that can be left after it, \
+    ~ if estimated from a sample";
+const CANDIDATE_HINT: &str = "Word, strategy score, the most words that can be left after it \
+    (~ if estimated from a sample) and how likely it is the answer";
+
+fn input(buffer: &mut String, height: f32) -> TextEdit<'_> {
+    TextEdit::singleline(buffer).font(FontId::monospace(height))
+}
+
+// Wide enough for the guess field, one tile per letter and the hard mode flag.
+fn window_width(length: usize) -> f32 {
+    (FIELD_SIZE.x + length as f32 * (TILE_SIZE.x + 8.0) + 44.0).max(298.0)
+}
+
+// Returns true if the guess or its feedback changed. Equations only take the characters they
+// can be made of.
+fn guess_row(
+    ui: &mut Ui,
+    guess: &mut Guess,
+    warning: Option<&str>,
+    height: f32,
+    accents: Accents,
+    nerdle: bool,
+) -> bool {
+    let mut changed = false;
+
+    let textedit = input(&mut guess.word, height).char_limit(guess.feedback.len());
+    if ui.add_sized(FIELD_SIZE, textedit).changed() {
+        guess.word = if nerdle {
+            guess
+                .word
+                .chars()
+                .filter(|&c| nerdle::ALPHABET.contains(c))
+                .collect()
+        } else {
+            word_list::fold(&guess.word, accents)
+        };
+        changed = true;
+    }
+
+    let mut chars = guess.word.chars();
+    for feedback in &mut guess.feedback {
+        let letter = chars.next().map(tile_letter);
+        let text = RichText::new(letter.unwrap_or_default())
+            .font(FontId::monospace(height))
+            .color(Color32::WHITE);
+
+        if ui
+            .add_sized(TILE_SIZE, Button::new(text).fill(tile_color(*feedback)))
+            .clicked()
+        {
+            *feedback = feedback.next();
+            changed = true;
+        }
+    }
+
+    if let Some(warning) = warning {
+        ui.label(RichText::new("⚠").color(Color32::RED))
+            .on_hover_text(warning);
+    }
+
+    changed
+}
+
+// A single letter even where the uppercase is longer, like ß turning into SS.
+fn tile_letter(ch: char) -> String {
+    match ch {
+        'ß' => "ẞ".to_string(),
+        _ => ch.to_uppercase().to_string(),
+    }
+}
+
+fn accents_label(accents: Accents) -> &'static str {
+    match accents {
+        Accents::Keep => "Keep accents",
+        Accents::Strip => "Strip accents",
+    }
+}
+
+fn tile_color(feedback: Feedback) -> Color32 {
+    match feedback {
+        Feedback::Grey => Color32::from_rgb(0x78, 0x7c, 0x7e),
+        Feedback::Yellow => Color32::from_rgb(0xc9, 0xb4, 0x58),
+        Feedback::Green => Color32::from_rgb(0x6a, 0xaa, 0x64),
+    }
+}
+
+fn pick_word_list(accents: Accents) -> Option<(PathBuf, Vec<String>, LoadReport)> {
+    let path = rfd::FileDialog::new().pick_file()?;
+    let (list, report) = word_list::read(&path, accents)?;
+
+    Some((path, list, report))
+}
+
+// Equations are checked by working them out, their list may still be generated.
+fn row_warnings(
+    length: usize,
+    guesses: &[Guess],
+    allowed: &Index,
+    hard_mode: bool,
+    nerdle: bool,
+) -> Vec<Option<String>> {
+    if !nerdle {
+        return guess_warnings(length, guesses, allowed.words(), hard_mode);
+    }
+
+    guess_warnings(length, guesses, &[], hard_mode)
+        .into_iter()
+        .zip(guesses)
+        .map(|(warning, guess)| {
+            if guess.is_complete() && !nerdle::is_valid(&guess.word) {
+                Some("Not a valid equation".to_string())
+            } else {
+                warning
+            }
+        })
+        .collect()
+}
+
+// Empty guesses for every board.
+fn new_boards(count: usize, length: usize) -> Vec<Vec<Guess>> {
+    vec![vec![Guess::new(length); rows(count)]; count]
+}
+
+// Lied colors would rule out the answer, so Fibble candidates are checked row by row and
+// hard mode doesn't apply.
+fn fibble_suggestions(
+    answers: &Index,
+    allowed: &Index,
+    guesses: &[Guess],
+    length: usize,
+    lies: usize,
+    prior: Option<&Prior>,
+    patterns: Option<&PatternMatrix>,
+) -> Suggestions {
+    Suggestions::from_possible(
+        answers,
+        fibble::possible(answers, length, guesses, lies),
+        allowed,
+        &allowed.of_length(length).iter().collect::<Vec<_>>(),
+        &fibble::LyingEntropy { lies },
+        prior,
+        patterns,
+    )
+}
+
+// Candidates also show how likely they are to be the answer. `words` is the list the guess was
+// ranked from.
+fn scored_text(scored: &Scored, words: &Index, probability: bool) -> String {
+    let Scored {
+        word,
+        score,
+        worst_case,
+        probability: p,
+    } = scored;
+
+    let mut text = format!("{} {score:.2}", words.words()[*word]);
+    match worst_case {
+        Some(WorstCase::Exact(count)) => text += &format!(" ≤{count}"),
+        Some(WorstCase::Estimate(count)) => text += &format!(" ≤~{count}"),
+        None => {}
+    }
+    if probability {
+        text += &format!(" {:.1}%", p * 100.0);
+    }
+
+    text
+}
+
+fn ranked_list(ui: &mut Ui, id: &str, len: usize, height: f32, text: impl Fn(usize) -> String) {
+    let area_content = |ui: &mut Ui, range: std::ops::Range<usize>| {
+        for row in range {
+            ui.label(RichText::new(text(row)).font(FontId::monospace(height)));
+        }
+    };
+
+    ScrollArea::vertical()
+        .id_salt(id)
+        .auto_shrink(false)
+        .show_rows(ui, height, len, area_content);
+}
+
+#[derive(Default)]
+struct LookaheadState {
+    done: usize,
+    total: usize,
+    result: Option<Vec<(String, f64)>>,
+}
+
+// A lookahead search running on a background thread, cancelled when dropped.
+struct LookaheadJob {
+    cancel: Arc<AtomicBool>,
+    state: Arc<Mutex<LookaheadState>>,
+}
+
+impl LookaheadJob {
+    fn start(ctx: &Context, suggestions: &Suggestions, answers: &Index, allowed: &Index) -> Self {
+        let owned = |words: Vec<&str>| words.into_iter().map(String::from).collect::<Vec<_>>();
+        let pool = owned(lookahead::pool(suggestions, answers, allowed));
+        let possible = owned(answers.words_at(&suggestions.possible));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let state = Arc::new(Mutex::new(LookaheadState {
+            total: pool.len(),
+            ..Default::default()
+        }));
+
+        let job = LookaheadJob {
+            cancel: cancel.clone(),
+            state: state.clone(),
+        };
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let pool = pool.iter().map(String::as_str).collect::<Vec<_>>();
+            let possible = possible.iter().map(String::as_str).collect::<Vec<_>>();
+            let result = lookahead::expected_guesses(&pool, &possible, |done, total| {
+                if cancel.load(Ordering::Relaxed) {
+                    return ControlFlow::Break(());
+                }
+
+                let mut state = state.lock().unwrap();
+                state.done = done;
+                state.total = total;
+                ctx.request_repaint();
+                ControlFlow::Continue(())
+            });
+
+            state.lock().unwrap().result = result;
+            ctx.request_repaint();
+        });
+
+        job
+    }
+}
+
+impl Drop for LookaheadJob {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+// Computes the patterns of the `length` letter words, or reads them from the cache directory.
+// `None` if there are too many words to keep them.
+fn spawn_patterns(
+    ctx: &Context,
+    length: usize,
+    answers: &Index,
+    allowed: &Index,
+) -> Option<JoinHandle<Option<PatternMatrix>>> {
+    let guesses = allowed.collect(&allowed.of_length(length));
+    let answers = answers.collect(&answers.of_length(length));
+    if PatternMatrix::bytes(&guesses, &answers) > PatternMatrix::MAX_BYTES {
+        return None;
+    }
+    let guesses = guesses.into_iter().map(String::from).collect::<Vec<_>>();
+    let answers = answers.into_iter().map(String::from).collect::<Vec<_>>();
+    let ctx = ctx.clone();
+
+    Some(thread::spawn(move || {
+        let guesses = guesses.iter().map(String::as_str).collect::<Vec<_>>();
+        let answers = answers.iter().map(String::as_str).collect::<Vec<_>>();
+        let matrix = match dirs::cache_dir() {
+            Some(dir) => PatternMatrix::cached(&guesses, &answers, &dir.join("wordle-helper")).map(
+                |(matrix, written)| {
+                    if let Err(err) = written {
+                        eprintln!("failed to cache patterns in {err}");
+                    }
+                    matrix
+                },
+            ),
+            None => PatternMatrix::compute(&guesses, &answers),
+        };
+        ctx.request_repaint();
+
+        matrix
+    }))
+}
+
+// The lists a ranking is made from, shared with the thread making it. Lists are replaced
+// instead of changed, so two are the same if they point to the same data.
+#[derive(Clone)]
+struct Lists {
+    playable: Arc<Index>,
+    guessable: Arc<Index>,
+    prior: Option<Arc<Prior>>,
+    patterns: Option<Arc<PatternMatrix>>,
+}
+
+impl PartialEq for Lists {
+    fn eq(&self, other: &Self) -> bool {
+        fn same<T>(a: &Option<Arc<T>>, b: &Option<Arc<T>>) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+        }
+
+        Arc::ptr_eq(&self.playable, &other.playable)
+            && Arc::ptr_eq(&self.guessable, &other.guessable)
+            && same(&self.prior, &other.prior)
+            && same(&self.patterns, &other.patterns)
+    }
+}
+
+// Everything the suggestions depend on. Only complete guesses are kept, so typing a word
+// doesn't rank again until it is complete.
+#[derive(Clone, PartialEq)]
+struct RankKey {
+    lists: Lists,
+    boards: Vec<Vec<Guess>>,
+    selected: usize,
+    length: usize,
+    hard_mode: bool,
+    strategy: &'static str,
+    absurdle: bool,
+    lies: usize,
+}
+
+// Suggestions for a key, the positions in them refer to the key's lists.
+struct Ranking {
+    key: RankKey,
+    suggestions: Suggestions,
+    all_boards: Boards,
+    // The guesses of the first board as the Absurdle host colored them, and the words it kept.
+    host: Option<(Vec<Guess>, usize)>,
+}
+
+impl Ranking {
+    fn new(mut key: RankKey) -> Self {
+        let host = key.absurdle.then(|| {
+            let playable = &key.lists.playable;
+            let kept = absurdle::host_replies(
+                &mut key.boards[0],
+                &playable.collect(&playable.of_length(key.length)),
+            );
+            (key.boards[0].clone(), kept.len())
+        });
+
+        let Lists {
+            playable,
+            guessable,
+            prior,
+            patterns,
+        } = &key.lists;
+        let chosen = strategy(key.strategy).unwrap_or(STRATEGIES[0]);
+        let suggestions = if key.lies > 0 {
+            fibble_suggestions(
+                playable,
+                guessable,
+                &key.boards[0],
+                key.length,
+                key.lies,
+                prior.as_deref(),
+                patterns.as_deref(),
+            )
+        } else {
+            Suggestions::new(
+                playable,
+                guessable,
+                &board_word(key.length, &key.boards[key.selected]),
+                key.hard_mode,
+                // The host keeps the largest group, so only the worst case matters.
+                if key.absurdle {
+                    &absurdle::HostKeeps
+                } else {
+                    chosen
+                },
+                prior.as_deref(),
+                patterns.as_deref(),
+            )
+        };
+        let all_boards = if key.boards.len() > 1 {
+            Boards::new(
+                playable,
+                key.length,
+                &key.boards,
+                chosen,
+                patterns.as_deref(),
+            )
+        } else {
+            Boards::default()
+        };
+
+        Ranking {
+            key,
+            suggestions,
+            all_boards,
+            host,
+        }
+    }
+}
+
+// Ranks on a background thread so the window stays responsive with long lists.
+fn spawn_ranking(ctx: &Context, key: RankKey) -> JoinHandle<Ranking> {
+    let ctx = ctx.clone();
+
+    thread::spawn(move || {
+        let ranking = Ranking::new(key);
+        ctx.request_repaint();
+
+        ranking
+    })
+}
+
+// Generates the equations of `length` characters, which takes a few seconds for Maxi.
+fn spawn_equations(ctx: &Context, length: usize) -> JoinHandle<Index> {
+    let ctx = ctx.clone();
+
+    thread::spawn(move || {
+        let equations = Index::new(nerdle::equations(length));
+        ctx.request_repaint();
+
+        equations
+    })
+}
+
+pub fn run() -> eframe::Result {
+    let config = Config::load();
+
+    let mut length = if config.nerdle {
+        if nerdle::LENGTHS.contains(&config.length) {
+            config.length
+        } else {
+            nerdle::DEFAULT_LENGTH
+        }
+    } else {
+        config.length.clamp(MIN_LENGTH, MAX_LENGTH)
+    };
+    let mut board_count = if BOARD_COUNTS.contains(&config.boards) {
+        config.boards
+    } else {
+        1
+    };
+    let mut boards = iter::once(config.guesses)
+        .chain(config.other_boards)
+        .collect::<Vec<_>>();
+    if boards.len() != board_count
+        || boards.iter().any(|guesses| {
+            guesses.len() != rows(board_count)
+                || guesses.iter().any(|guess| guess.feedback.len() != length)
+        })
+    {
+        boards = new_boards(board_count, length);
+    }
+    let mut selected = 0;
+
+    let mut accents = config.accents;
+    let mut answer_path = config.answer_list;
+    let mut answers = Arc::new(Index::new(
+        match answer_path
+            .as_deref()
+            .and_then(|path| word_list::read(path, accents))
+        {
+            Some((list, _)) => list,
+            None => {
+                answer_path = None;
+                word_list::default_answers()
+            }
+        },
+    ));
+    let mut guess_path = config.guess_list;
+    let mut guess_list = match guess_path
+        .as_deref()
+        .and_then(|path| word_list::read(path, accents))
+    {
+        Some((list, _)) => list,
+        None => {
+            guess_path = None;
+            Vec::new()
+        }
+    };
+    let mut allowed = Arc::new(Index::new(word_list::merge(answers.words(), &guess_list)));
+    let mut prior_path = config.frequencies;
+    let mut prior = prior_path
+        .as_deref()
+        .and_then(|path| Prior::read(path, accents).ok())
+        .map(Arc::new);
+    if prior.is_none() {
+        prior_path = None;
+    }
+    let mut tree_path = config.tree;
+    let mut tree = tree_path.as_deref().and_then(|path| Tree::load(path).ok());
+    if tree.is_none() {
+        tree_path = None;
+    }
+    let mut load_report: Option<String> = None;
+
+    let mut hard_mode = config.hard_mode;
+    let mut strategy = strategy(&config.strategy).unwrap_or(STRATEGIES[0]);
+    let mut play_absurdle = config.absurdle;
+    let mut lies = config.lies;
+    let mut play_nerdle = config.nerdle;
+    let mut equations = Arc::new(Index::default());
+    let mut equations_job: Option<JoinHandle<Index>> = None;
+    let mut equations_length = None;
+    let mut warnings = row_warnings(
+        length,
+        &boards[selected],
+        &allowed,
+        hard_mode && lies == 0,
+        play_nerdle,
+    );
+    let mut ranking: Option<Ranking> = None;
+    let mut ranking_job: Option<JoinHandle<Ranking>> = None;
+    let mut rank_key: Option<RankKey> = None;
+    let mut use_lookahead = config.lookahead;
+    let mut lookahead_job: Option<LookaheadJob> = None;
+    let mut cache_patterns = config.cache_patterns;
+    let mut patterns: Option<Arc<PatternMatrix>> = None;
+    let mut pattern_job: Option<JoinHandle<Option<PatternMatrix>>> = None;
+    let mut pattern_key = None;
+    let mut too_many_patterns = false;
+
+    let options = eframe::NativeOptions {
+        viewport: ViewportBuilder::default()
+            .with_inner_size([window_width(length), WINDOW_HEIGHT])
+            .with_resizable(false),
+        ..Default::default()
+    };
+
+    eframe::run_simple_native("Wordle Helper", options, move |ctx, _frame| {
+        CentralPanel::default().show(ctx, |ui| {
+            let monospace_height: f32 = ui.text_style_height(&TextStyle::Monospace);
+
+            ui.vertical_centered(|ui| {
+                ui.heading("Guesses");
+            });
+            let mut changed = false;
+            let mut forget = false;
+            if board_count > 1 {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Board");
+                    for board in 0..board_count {
+                        let possible = ranking
+                            .as_ref()
+                            .and_then(|ranking| ranking.all_boards.possible.get(board));
+                        let text = match possible {
+                            Some(possible) if possible.len() == 1 => format!("{}✔", board + 1),
+                            _ => (board + 1).to_string(),
+                        };
+                        if ui.selectable_label(selected == board, text).clicked() {
+                            selected = board;
+                            changed = true;
+                        }
+                    }
+                });
+            }
+
+            let mut edited = false;
+            ScrollArea::vertical()
+                .id_salt("guesses")
+                .max_height(ROWS as f32 * (TILE_SIZE.y + 6.0))
+                .show(ui, |ui| {
+                    for (idx, guess) in boards[selected].iter_mut().enumerate() {
+                        let warning = warnings.get(idx).and_then(Option::as_deref);
+                        ui.horizontal(|ui| {
+                            edited |= guess_row(
+                                ui,
+                                guess,
+                                warning,
+                                monospace_height,
+                                accents,
+                                play_nerdle,
+                            );
+                        });
+                    }
+                });
+            // Every board shares the guessed words, only the colors differ.
+            if edited {
+                let words = boards[selected]
+                    .iter()
+                    .map(|guess| guess.word.clone())
+                    .collect::<Vec<_>>();
+                for board in &mut boards {
+                    for (guess, w) in board.iter_mut().zip(&words) {
+                        guess.word.clone_from(w);
+                    }
+                }
+                changed = true;
+            }
+
+            ui.add_space(10.0);
+            ui.horizontal_wrapped(|ui| {
+                if ui.button("Reset").clicked() {
+                    boards = new_boards(board_count, length);
+                    selected = 0;
+                    changed = true;
+                }
+
+                if ui.button("Open answer list…").clicked()
+                    && let Some((path, list, report)) = pick_word_list(accents)
+                {
+                    answer_path = Some(path);
+                    answers = Arc::new(Index::new(list));
+                    allowed = Arc::new(Index::new(word_list::merge(answers.words(), &guess_list)));
+                    load_report = Some(report.summary());
+                    changed = true;
+                }
+
+                if ui.button("Open guess list…").clicked()
+                    && let Some((path, list, report)) = pick_word_list(accents)
+                {
+                    guess_path = Some(path);
+                    guess_list = list;
+                    allowed = Arc::new(Index::new(word_list::merge(answers.words(), &guess_list)));
+                    load_report = Some(report.summary());
+                    changed = true;
+                }
+
+                let prior_button = ui
+                    .button("Open frequencies…")
+                    .on_hover_text("One word and how common it is per line");
+                if prior_button.clicked()
+                    && let Some(path) = rfd::FileDialog::new().pick_file()
+                {
+                    match Prior::read(&path, accents) {
+                        Ok(loaded) => {
+                            load_report = Some(format!("Loaded {} frequencies", loaded.len()));
+                            prior = Some(Arc::new(loaded));
+                            prior_path = Some(path);
+                        }
+                        Err(err) => {
+                            load_report = Some(format!("Can't open the frequencies: {err}"))
+                        }
+                    }
+                    changed = true;
+                }
+
+                let tree_button = ui
+                    .button("Open tree…")
+                    .on_hover_text("A decision tree saved by the tree command");
+                if tree_button.clicked()
+                    && let Some(path) = rfd::FileDialog::new().pick_file()
+                {
+                    match Tree::load(&path) {
+                        Ok(loaded) => {
+                            let mut report = format!("Loaded a tree of {} guesses", loaded.size());
+                            if loaded.depth() > ROWS {
+                                report +=
+                                    &format!(", some answers need {} guesses", loaded.depth());
+                            }
+                            load_report = Some(report);
+                            tree = Some(loaded);
+                            tree_path = Some(path);
+                        }
+                        Err(err) => load_report = Some(format!("Can't open the tree: {err}")),
+                    }
+                    changed = true;
+                }
+
+                let forget_button = ui
+                    .button("Forget")
+                    .on_hover_text("Delete the saved session and go back to the built-in list");
+                if forget_button.clicked() {
+                    if let Err(err) = Config::forget() {
+                        eprintln!("failed to delete config: {err}");
+                    }
+
+                    answer_path = None;
+                    answers = Arc::new(Index::new(word_list::default_answers()));
+                    guess_path = None;
+                    guess_list.clear();
+                    prior_path = None;
+                    prior = None;
+                    tree_path = None;
+                    tree = None;
+                    allowed = Arc::new(Index::new(word_list::merge(answers.words(), &guess_list)));
+                    load_report = None;
+                    hard_mode = false;
+                    use_lookahead = false;
+                    cache_patterns = false;
+                    play_absurdle = false;
+                    lies = 0;
+                    play_nerdle = false;
+                    accents = Accents::Keep;
+                    strategy = STRATEGIES[0];
+                    length = DEFAULT_LENGTH;
+                    board_count = 1;
+                    boards = new_boards(board_count, length);
+                    selected = 0;
+                    ctx.send_viewport_cmd(ViewportCommand::InnerSize(Vec2::new(
+                        window_width(length),
+                        WINDOW_HEIGHT,
+                    )));
+                    changed = true;
+                    forget = true;
+                }
+            });
+            if let Some(report) = &load_report {
+                let dismissed = ui
+                    .horizontal(|ui| {
+                        ui.label(report);
+                        ui.small_button("✖").clicked()
+                    })
+                    .inner;
+                if dismissed {
+                    load_report = None;
+                }
+            }
+            ui.horizontal(|ui| {
+                changed |= ui
+                    .add_enabled(lies == 0, Checkbox::new(&mut hard_mode, "Hard mode"))
+                    .changed();
+                changed |= ui
+                    .checkbox(&mut use_lookahead, "Lookahead")
+                    .on_hover_text(format!(
+                        "Search two guesses deep when {} to {} candidates are left",
+                        lookahead::MIN_CANDIDATES,
+                        lookahead::MAX_CANDIDATES
+                    ))
+                    .changed();
+                changed |= ui
+                    .add_enabled(
+                        board_count == 1 && lies == 0,
+                        Checkbox::new(&mut play_absurdle, "Absurdle"),
+                    )
+                    .on_hover_text("The host colors the guesses, keeping as many words as it can")
+                    .changed();
+
+                ui.label("Lies");
+                changed |= ui
+                    .add_enabled(
+                        board_count == 1 && !play_absurdle,
+                        DragValue::new(&mut lies).range(0..=2),
+                    )
+                    .on_hover_text("Fibble: tiles colored wrong in every guess, 0 for Wordle")
+                    .changed();
+
+                let nerdle_box = ui
+                    .checkbox(&mut play_nerdle, "Nerdle")
+                    .on_hover_text("Guess equations like 12+35=47 instead of words");
+                if nerdle_box.changed() {
+                    length = if play_nerdle {
+                        nerdle::DEFAULT_LENGTH
+                    } else {
+                        DEFAULT_LENGTH
+                    };
+                }
+
+                ui.label("Letters");
+                let letters_changed = if play_nerdle {
+                    let mut picked = false;
+                    ComboBox::from_id_salt("equation length")
+                        .selected_text(length.to_string())
+                        .show_ui(ui, |ui| {
+                            for option in nerdle::LENGTHS {
+                                picked |= ui
+                                    .selectable_value(&mut length, option, option.to_string())
+                                    .changed();
+                            }
+                        });
+                    picked
+                } else {
+                    ui.add(DragValue::new(&mut length).range(MIN_LENGTH..=MAX_LENGTH))
+                        .changed()
+                };
+                if letters_changed || nerdle_box.changed() {
+                    boards = new_boards(board_count, length);
+                    selected = 0;
+                    ctx.send_viewport_cmd(ViewportCommand::InnerSize(Vec2::new(
+                        window_width(length),
+                        WINDOW_HEIGHT,
+                    )));
+                    changed = true;
+                }
+            });
+            // Fibble ranks by its own entropy over the shown feedback, whatever is picked here.
+            ui.add_enabled_ui(lies == 0, |ui| {
+                ComboBox::from_label("Strategy")
+                    .selected_text(strategy.label())
+                    .show_ui(ui, |ui| {
+                        for option in STRATEGIES {
+                            let selected = strategy.name() == option.name();
+                            if ui.selectable_label(selected, option.label()).clicked() && !selected
+                            {
+                                strategy = option;
+                                changed = true;
+                            }
+                        }
+                    });
+            });
+            let mut refold = false;
+            ComboBox::from_label("Accents")
+                .selected_text(accents_label(accents))
+                .show_ui(ui, |ui| {
+                    for option in Accents::ALL {
+                        let selected = accents == option;
+                        if ui
+                            .selectable_label(selected, accents_label(option))
+                            .clicked()
+                            && !selected
+                        {
+                            accents = option;
+                            refold = true;
+                        }
+                    }
+                })
+                .response
+                .on_hover_text("Whether accented letters like ñ are letters of their own");
+            // The lists, frequencies and guesses were folded the other way.
+            if refold {
+                if let Some((list, _)) = answer_path
+                    .as_deref()
+                    .and_then(|path| word_list::read(path, accents))
+                {
+                    answers = Arc::new(Index::new(list));
+                }
+                if let Some((list, _)) = guess_path
+                    .as_deref()
+                    .and_then(|path| word_list::read(path, accents))
+                {
+                    guess_list = list;
+                }
+                allowed = Arc::new(Index::new(word_list::merge(answers.words(), &guess_list)));
+                prior = prior_path
+                    .as_deref()
+                    .and_then(|path| Prior::read(path, accents).ok())
+                    .map(Arc::new);
+                for guess in boards.iter_mut().flatten() {
+                    guess.word = word_list::fold(&guess.word, accents);
+                }
+                changed = true;
+            }
+            ComboBox::from_label("Boards")
+                .selected_text(board_count.to_string())
+                .show_ui(ui, |ui| {
+                    for option in BOARD_COUNTS {
+                        let selected_count = board_count == option;
+                        if ui
+                            .selectable_label(selected_count, option.to_string())
+                            .clicked()
+                            && !selected_count
+                        {
+                            board_count = option;
+                            play_absurdle = false;
+                            lies = 0;
+                            boards = new_boards(board_count, length);
+                            selected = 0;
+                            changed = true;
+                        }
+                    }
+                });
+
+            let pattern_box = ui
+                .checkbox(&mut cache_patterns, "Precompute patterns")
+                .on_hover_text(
+                    "Work out the feedback of every guess against every answer once and keep \
+                    it on disk, about 220 MB for the built-in list",
+                );
+            changed |= pattern_box.changed();
+
+            // Equations are generated for the length that is played.
+            let wanted = play_nerdle.then_some(length);
+            if equations_length != wanted {
+                equations = Arc::new(Index::default());
+                equations_job = wanted.map(|length| spawn_equations(ctx, length));
+                equations_length = wanted;
+            }
+            if equations_job.as_ref().is_some_and(JoinHandle::is_finished)
+                && let Some(job) = equations_job.take()
+            {
+                equations = Arc::new(job.join().unwrap_or_default());
+            }
+
+            // Only worth it for strategies that look the patterns up, once the words are there.
+            let uses_patterns = if lies > 0 {
+                false
+            } else if play_absurdle {
+                absurdle::HostKeeps.uses_patterns()
+            } else {
+                strategy.uses_patterns()
+            };
+            let key = (cache_patterns && uses_patterns && equations_job.is_none()).then(|| {
+                (
+                    length,
+                    answer_path.clone(),
+                    guess_path.clone(),
+                    accents,
+                    play_nerdle,
+                )
+            });
+            if pattern_key != key {
+                patterns = None;
+                pattern_job = None;
+                too_many_patterns = false;
+                if key.is_some() {
+                    let (playable, guessable) = if play_nerdle {
+                        (&equations, &equations)
+                    } else {
+                        (&answers, &allowed)
+                    };
+                    pattern_job = spawn_patterns(ctx, length, playable, guessable);
+                    too_many_patterns = pattern_job.is_none();
+                }
+                pattern_key = key;
+            }
+            if pattern_job.as_ref().is_some_and(JoinHandle::is_finished)
+                && let Some(job) = pattern_job.take()
+            {
+                patterns = job.join().ok().flatten().map(Arc::new);
+            }
+
+            // The last ranking stays up until the next one is done. The host's colors are
+            // taken over if the guesses are still the same.
+            if ranking_job.as_ref().is_some_and(JoinHandle::is_finished)
+                && let Some(job) = ranking_job.take()
+                && let Ok(done) = job.join()
+            {
+                if let Some((colored, _)) = &done.host
+                    && boards[0]
+                        .iter()
+                        .filter(|guess| guess.is_complete())
+                        .map(|guess| &guess.word)
+                        .eq(colored.iter().map(|guess| &guess.word))
+                {
+                    let complete = boards[0].iter_mut().filter(|guess| guess.is_complete());
+                    for (guess, host) in complete.zip(colored) {
+                        changed |= guess.feedback != host.feedback;
+                        guess.feedback.clone_from(&host.feedback);
+                    }
+                }
+                rank_key = Some(done.key.clone());
+                ranking = Some(done);
+                lookahead_job = None;
+            }
+
+            let (playable, guessable) = if play_nerdle {
+                (&equations, &equations)
+            } else {
+                (&answers, &allowed)
+            };
+            let key = RankKey {
+                lists: Lists {
+                    playable: playable.clone(),
+                    guessable: guessable.clone(),
+                    prior: prior.clone(),
+                    patterns: patterns.clone(),
+                },
+                boards: boards
+                    .iter()
+                    .map(|guesses| {
+                        guesses
+                            .iter()
+                            .filter(|guess| guess.is_complete())
+                            .cloned()
+                            .collect()
+                    })
+                    .collect(),
+                selected,
+                length,
+                hard_mode,
+                strategy: strategy.name(),
+                absurdle: play_absurdle,
+                lies,
+            };
+            // One ranking at a time, the latest key is ranked once the running one is done.
+            if ranking_job.is_none() && rank_key.as_ref() != Some(&key) {
+                rank_key = Some(key.clone());
+                ranking_job = Some(spawn_ranking(ctx, key));
+            }
+
+            if changed {
+                warnings = row_warnings(
+                    length,
+                    &boards[selected],
+                    guessable,
+                    hard_mode && lies == 0,
+                    play_nerdle,
+                );
+
+                let config = Config {
+                    answer_list: answer_path.clone(),
+                    guess_list: guess_path.clone(),
+                    frequencies: prior_path.clone(),
+                    tree: tree_path.clone(),
+                    length,
+                    hard_mode,
+                    strategy: strategy.name().to_string(),
+                    lookahead: use_lookahead,
+                    cache_patterns,
+                    absurdle: play_absurdle,
+                    lies,
+                    nerdle: play_nerdle,
+                    accents,
+                    boards: board_count,
+                    guesses: boards[0].clone(),
+                    other_boards: boards[1..].to_vec(),
+                };
+                if !forget && let Err(err) = config.save() {
+                    eprintln!("failed to save config: {err}");
+                }
+            }
+
+            // Lookahead and the tree assume every color is true.
+            if !use_lookahead || lies > 0 {
+                lookahead_job = None;
+            }
+            if let Some(ranking) = &ranking
+                && lookahead_job.is_none()
+                && use_lookahead
+                && ranking.key.lies == 0
+                && lookahead::is_endgame(ranking.suggestions.possible.len())
+            {
+                let Lists {
+                    playable,
+                    guessable,
+                    ..
+                } = &ranking.key.lists;
+                lookahead_job = Some(LookaheadJob::start(
+                    ctx,
+                    &ranking.suggestions,
+                    playable,
+                    guessable,
+                ));
+            }
+
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if let Some(ranking) = &ranking {
+                    let possible = ranking.suggestions.possible.len();
+                    if possible > MAX_RANKED {
+                        ui.label(format!(
+                            "{possible} possible words, a sample of {MAX_RANKED} is ranked"
+                        ));
+                    } else {
+                        ui.label(format!("{possible} possible words"));
+                    }
+                }
+                if ranking_job.is_some() {
+                    ui.spinner();
+                }
+            });
+            if equations_job.is_some() {
+                ui.small("Generating equations…");
+            }
+            if pattern_job.is_some() {
+                ui.small("Precomputing feedback patterns…");
+            }
+            if too_many_patterns {
+                ui.small("Too many words to precompute their feedback patterns");
+            }
+            if let Some(ranking) = &ranking {
+                if let Some((colored, kept)) = &ranking.host
+                    && let Some(last) = colored.last()
+                {
+                    ui.label(format!(
+                        "The host answers \"{}\" keeping {kept} words",
+                        last.word
+                    ));
+                }
+                if let Some(probe) = ranking.suggestions.better_probe {
+                    ui.label(format!(
+                        "\"{}\" can't be the answer but beats every candidate",
+                        ranking.key.lists.guessable.words()[probe]
+                    ));
+                }
+            }
+            if let Some(tree) = &tree
+                && board_count == 1
+                && lies == 0
+                && !play_nerdle
+            {
+                match tree.next_guess(&boards[0]) {
+                    Next::Guess(next) => ui.label(format!("The tree guesses \"{next}\" next")),
+                    Next::Solved => ui.label("The tree found the answer"),
+                    Next::Left => ui.label("The guesses left the tree"),
+                };
+            }
+
+            if let Some(job) = &lookahead_job {
+                let state = job.state.lock().unwrap();
+                match &state.result {
+                    Some(result) => {
+                        ui.label("Lookahead, expected guesses to finish:");
+                        for (w, expected) in result.iter().take(3) {
+                            ui.label(
+                                RichText::new(format!("{w} {expected:.3}"))
+                                    .font(FontId::monospace(monospace_height)),
+                            );
+                        }
+                    }
+                    None => {
+                        let progress = state.done as f32 / state.total.max(1) as f32;
+                        ui.add(ProgressBar::new(progress).text("Looking ahead…"));
+                    }
+                }
+            }
+
+            let Some(Ranking {
+                key,
+                suggestions,
+                all_boards,
+                ..
+            }) = &ranking
+            else {
+                return;
+            };
+            let Lists {
+                playable,
+                guessable,
+                ..
+            } = &key.lists;
+            ui.columns(2, |columns| {
+                columns[0].label("Candidates").on_hover_text(CANDIDATE_HINT);
+                ranked_list(
+                    &mut columns[0],
+                    "candidates",
+                    suggestions.ranked.len(),
+                    monospace_height,
+                    |row| scored_text(&suggestions.ranked[row], playable, true),
+                );
+
+                if board_count > 1 {
+                    columns[1].label("Best for all boards").on_hover_text(
+                        "Known answers of the unsolved boards first, then words by their \
+                         score summed over the unsolved boards",
+                    );
+                    if all_boards.candidates > MAX_RANKED {
+                        columns[1].small(format!(
+                            "{} candidates, a sample of {MAX_RANKED} is ranked",
+                            all_boards.candidates
+                        ));
+                    }
+                    ranked_list(
+                        &mut columns[1],
+                        "all boards",
+                        all_boards.ranked.len(),
+                        monospace_height,
+                        |row| {
+                            let (w, score) = all_boards.ranked[row];
+                            format!("{} {score:.2}", playable.words()[w])
+                        },
+                    );
+                } else {
+                    columns[1].label("Best probes").on_hover_text(SCORE_HINT);
+                    ranked_list(
+                        &mut columns[1],
+                        "probes",
+                        suggestions.probes.len(),
+                        monospace_height,
+                        |row| scored_text(&suggestions.probes[row], guessable, false),
+                    );
+                }
+            });
+        });
+    })
+}
@@ -0,0 +1,231 @@
+//! Bitset index over a word list, so constraints are checked against every word at once.
+
+use std::collections::HashMap;
+
+use crate::Word;
+
+/// A set of positions in an [`Index`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bitset {
+    blocks: Vec<u64>,
+}
+
+impl Bitset {
+    fn empty(len: usize) -> Self {
+        Bitset {
+            blocks: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    fn insert(&mut self, idx: usize) {
+        self.blocks[idx / 64] |= 1 << (idx % 64);
+    }
+
+    fn intersect(&mut self, other: &Bitset) {
+        for (block, other) in self.blocks.iter_mut().zip(&other.blocks) {
+            *block &= other;
+        }
+    }
+
+    fn remove_all(&mut self, other: &Bitset) {
+        for (block, other) in self.blocks.iter_mut().zip(&other.blocks) {
+            *block &= !other;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.blocks.fill(0);
+    }
+
+    pub fn contains(&self, idx: usize) -> bool {
+        self.blocks
+            .get(idx / 64)
+            .is_some_and(|block| block & (1 << (idx % 64)) != 0)
+    }
+
+    /// Number of positions in the set.
+    pub fn len(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(|block| block.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.iter().all(|&block| block == 0)
+    }
+
+    /// Every position in the set, ascending.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.blocks.iter().enumerate().flat_map(|(idx, &block)| {
+            let mut block = block;
+            std::iter::from_fn(move || {
+                (block != 0).then(|| {
+                    let bit = block.trailing_zeros() as usize;
+                    block &= block - 1;
+                    idx * 64 + bit
+                })
+            })
+        })
+    }
+}
+
+/// A word list with a [`Bitset`] of the words for every letter at every position and every
+/// minimum number of copies of a letter.
+#[derive(Clone, Debug, Default)]
+pub struct Index {
+    words: Vec<String>,
+    lengths: HashMap<usize, Bitset>,
+    at: HashMap<(usize, char), Bitset>,
+    // Words containing the letter at least this many times.
+    at_least: HashMap<(char, usize), Bitset>,
+}
+
+impl Index {
+    /// Indexes `words`, keeping their order.
+    pub fn new(words: Vec<String>) -> Self {
+        let len = words.len();
+        let mut lengths = HashMap::new();
+        let mut at = HashMap::new();
+        let mut at_least = HashMap::new();
+
+        for (idx, w) in words.iter().enumerate() {
+            let mut counts: HashMap<char, usize> = HashMap::new();
+            for (pos, ch) in w.chars().enumerate() {
+                at.entry((pos, ch))
+                    .or_insert_with(|| Bitset::empty(len))
+                    .insert(idx);
+
+                let count = counts.entry(ch).or_default();
+                *count += 1;
+                at_least
+                    .entry((ch, *count))
+                    .or_insert_with(|| Bitset::empty(len))
+                    .insert(idx);
+            }
+
+            lengths
+                .entry(w.chars().count())
+                .or_insert_with(|| Bitset::empty(len))
+                .insert(idx);
+        }
+
+        Index {
+            words,
+            lengths,
+            at,
+            at_least,
+        }
+    }
+
+    /// Every indexed word, in the original order.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Positions of the words `word` allows, same as [`Word::filter`].
+    pub fn matches(&self, word: &Word) -> Bitset {
+        let mut matches = self.of_length(word.len());
+
+        for (pos, ch) in word.greens() {
+            keep(&mut matches, self.at.get(&(pos, ch)));
+        }
+        for (pos, ch) in word.excluded() {
+            if let Some(words) = self.at.get(&(pos, ch)) {
+                matches.remove_all(words);
+            }
+        }
+        for (ch, min, max) in word.bounds() {
+            if min > 0 {
+                keep(&mut matches, self.at_least.get(&(ch, min)));
+            }
+            if let Some(words) = max
+                .checked_add(1)
+                .and_then(|too_many| self.at_least.get(&(ch, too_many)))
+            {
+                matches.remove_all(words);
+            }
+        }
+
+        matches
+    }
+
+    /// Positions of the words that are valid guesses in hard mode, same as
+    /// [`Word::allows_in_hard_mode`].
+    pub fn hard_mode_matches(&self, word: &Word) -> Bitset {
+        let mut matches = self.of_length(word.len());
+
+        for (pos, ch) in word.greens() {
+            keep(&mut matches, self.at.get(&(pos, ch)));
+        }
+        for (ch, min, _) in word.bounds() {
+            if min > 0 {
+                keep(&mut matches, self.at_least.get(&(ch, min)));
+            }
+        }
+
+        matches
+    }
+
+    /// The words at the positions in `set`.
+    pub fn collect(&self, set: &Bitset) -> Vec<&str> {
+        set.iter().map(|idx| self.words[idx].as_str()).collect()
+    }
+
+    /// The words at `positions`, in that order.
+    pub fn words_at(&self, positions: &[usize]) -> Vec<&str> {
+        positions
+            .iter()
+            .map(|&idx| self.words[idx].as_str())
+            .collect()
+    }
+
+    /// Positions of the words of `length` letters.
+    pub fn of_length(&self, length: usize) -> Bitset {
+        self.lengths
+            .get(&length)
+            .cloned()
+            .unwrap_or_else(|| Bitset::empty(self.words.len()))
+    }
+}
+
+// No word has a letter combination that was never indexed.
+fn keep(matches: &mut Bitset, words: Option<&Bitset>) {
+    match words {
+        Some(words) => matches.intersect(words),
+        None => matches.clear(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Guess, word_list};
+
+    #[test]
+    fn matches_agree_with_filter() {
+        let answers = word_list::default_answers();
+        let index = Index::new(answers.clone());
+
+        for guesses in [
+            &["crane:bbbbb"][..],
+            &["speed:gbgbb"],
+            &["eerie:ybbbb", "ahead:bbgyb"],
+            &["llama:yybbb"],
+            &["geese:bbggg"],
+        ] {
+            let guesses = guesses
+                .iter()
+                .map(|guess| Guess::parse(guess).unwrap())
+                .collect::<Vec<_>>();
+            let word = Word::from_guesses(5, &guesses);
+            let filtered = answers
+                .iter()
+                .map(String::as_str)
+                .filter(|w| word.filter(w).is_some())
+                .collect::<Vec<_>>();
+
+            assert_eq!(index.collect(&index.matches(&word)), filtered);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Filtering and ranking for Wordle and its variants, without any user interface.
+//!
+//! Build a [`Word`] from the [`Guess`]es made so far, then let [`Suggestions`] work out the
+//! remaining candidates and the most informative next guesses:
+//!
+//! ```
+//! use wordle_helper::{Entropy, Guess, Index, Suggestions, Word, word_list};
+//!
+//! let answers = word_list::default_answers();
+//! let allowed = Index::new(word_list::merge(&answers, &[]));
+//! let answers = Index::new(answers);
+//! let guesses = [Guess::parse("crane:bybbg").unwrap()];
+//!
+//! let word = Word::from_guesses(5, &guesses);
+//! let suggestions = Suggestions::new(&answers, &allowed, &word, false, &Entropy, None, None);
+//! let possible = answers.words_at(&suggestions.possible);
+//! assert!(possible.iter().all(|w| w.ends_with('e')));
+//! ```
+
+pub mod absurdle;
+pub mod bench;
+pub mod boards;
+mod feedback;
+pub mod fibble;
+mod index;
+pub mod lookahead;
+mod matrix;
+pub mod nerdle;
+mod prior;
+mod rank;
+mod strategy;
+pub mod tree;
+mod word;
+pub mod word_list;
+
+pub use feedback::{Feedback, Guess, feedback, pattern, pattern_index};
+pub use index::{Bitset, Index};
+pub use matrix::PatternMatrix;
+pub use prior::Prior;
+pub use rank::{MAX_RANKED, Scored, Suggestions, distinct_letters};
+pub use strategy::{
+    DistinctLetters, Entropy, ExpectedSize, LetterFrequency, Minimax, PositionalFrequency,
+    STRATEGIES, ScoreContext, Strategy, WorstCase, strategy,
+};
+pub use tree::Tree;
+pub use word::{Word, guess_warnings};
+
+/// Number of guesses the game allows.
+pub const ROWS: usize = 6;
+/// Word length the game is played with unless configured otherwise.
+pub const DEFAULT_LENGTH: usize = 5;
+/// Shortest supported word length.
+pub const MIN_LENGTH: usize = 4;
+/// Longest supported word length.
+pub const MAX_LENGTH: usize = 11;
@@ -0,0 +1,171 @@
+//! Two step lookahead for the endgame, where single step scores often pick badly.
+
+use std::collections::BTreeMap;
+use std::ops::ControlFlow;
+
+use crate::{Index, Suggestions, feedback, pattern_index};
+
+/// Fewest candidates the lookahead is worth running for.
+pub const MIN_CANDIDATES: usize = 3;
+/// Most candidates the lookahead runs for, beyond that it gets slow.
+pub const MAX_CANDIDATES: usize = 30;
+/// How many of the best probes are considered besides the candidates.
+pub const PROBES: usize = 200;
+
+/// Whether the lookahead is worth running for this many candidates.
+pub fn is_endgame(candidates: usize) -> bool {
+    (MIN_CANDIDATES..=MAX_CANDIDATES).contains(&candidates)
+}
+
+/// The guesses worth looking at: every candidate and the best [`PROBES`] probes, with
+/// `suggestions` ranked from `answers` and `allowed`.
+pub fn pool<'a>(suggestions: &Suggestions, answers: &'a Index, allowed: &'a Index) -> Vec<&'a str> {
+    let mut pool = answers.words_at(&suggestions.possible);
+    for probe in suggestions.probes.iter().take(PROBES) {
+        let probe = allowed.words()[probe.word].as_str();
+        if !pool.contains(&probe) {
+            pool.push(probe);
+        }
+    }
+
+    pool
+}
+
+// Expected number of guesses to find one of `size` equally likely candidates by guessing them in
+// turn, assuming every wrong guess rules out all others. An optimistic estimate for deeper plies.
+fn leaf(size: usize) -> f64 {
+    match size {
+        0 => 0.0,
+        _ => (2 * size - 1) as f64 / size as f64,
+    }
+}
+
+struct Search {
+    // patterns[guess][answer] for every guess of the pool and every candidate.
+    patterns: Vec<Vec<usize>>,
+    solved: usize,
+}
+
+impl Search {
+    // Groups `answers` by the feedback `guess` gets for them. The group that means the guess
+    // was right is left out, it needs no further guesses.
+    fn partition(&self, guess: usize, answers: &[usize]) -> Vec<Vec<usize>> {
+        let mut buckets: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for &answer in answers {
+            let pattern = self.patterns[guess][answer];
+            if pattern != self.solved {
+                buckets.entry(pattern).or_default().push(answer);
+            }
+        }
+
+        buckets.into_values().collect()
+    }
+
+    // Expected guesses to finish `answers` after guessing `guess`, with leaf estimates below.
+    fn one_ply(&self, guess: usize, answers: &[usize]) -> f64 {
+        let total = answers.len() as f64;
+
+        self.partition(guess, answers)
+            .iter()
+            .map(|bucket| bucket.len() as f64 / total * leaf(bucket.len()))
+            .sum()
+    }
+
+    // Like `one_ply`, but every group left over gets the best follow up guess of the pool.
+    fn two_ply(&self, guess: usize, answers: &[usize]) -> f64 {
+        let total = answers.len() as f64;
+
+        self.partition(guess, answers)
+            .iter()
+            .map(|bucket| {
+                let cost = match bucket.len() {
+                    1 | 2 => leaf(bucket.len()),
+                    _ => {
+                        1.0 + (0..self.patterns.len())
+                            .map(|next| self.one_ply(next, bucket))
+                            .fold(f64::INFINITY, f64::min)
+                    }
+                };
+
+                bucket.len() as f64 / total * cost
+            })
+            .sum()
+    }
+}
+
+/// Expected number of guesses to finish, including the guess itself, for every word in `pool`
+/// against the remaining `possible` answers, best first.
+///
+/// `progress` is called with the number of guesses done and the total after each guess and can
+/// stop the search early, in which case `None` is returned.
+pub fn expected_guesses(
+    pool: &[&str],
+    possible: &[&str],
+    mut progress: impl FnMut(usize, usize) -> ControlFlow<()>,
+) -> Option<Vec<(String, f64)>> {
+    let answers = possible
+        .iter()
+        .map(|w| w.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let patterns = pool
+        .iter()
+        .map(|guess| {
+            let guess = guess.chars().collect::<Vec<_>>();
+            answers
+                .iter()
+                .map(|answer| pattern_index(&feedback(&guess, answer)))
+                .collect()
+        })
+        .collect();
+    let solved = answers
+        .first()
+        .map_or(0, |answer| pattern_index(&feedback(answer, answer)));
+    let search = Search { patterns, solved };
+
+    let all = (0..answers.len()).collect::<Vec<_>>();
+    let mut scored = Vec::with_capacity(pool.len());
+    for (idx, guess) in pool.iter().enumerate() {
+        scored.push((guess.to_string(), 1.0 + search.two_ply(idx, &all)));
+
+        if progress(idx + 1, pool.len()).is_break() {
+            return None;
+        }
+    }
+    scored.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    Some(scored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected(pool: &[&str], possible: &[&str]) -> Vec<(String, f64)> {
+        expected_guesses(pool, possible, |_, _| ControlFlow::Continue(())).unwrap()
+    }
+
+    #[test]
+    fn separating_candidate_takes_five_thirds() {
+        // "crate" and "grate" give the other two candidates different feedback, "slate" can't
+        // tell "crate" from "grate".
+        let possible = ["crate", "grate", "slate"];
+        let scored = expected(&possible, &possible);
+        let score = |w: &str| scored.iter().find(|(guess, _)| guess == w).unwrap().1;
+
+        assert!((score("crate") - 5.0 / 3.0).abs() < 1e-9);
+        assert!((score("grate") - 5.0 / 3.0).abs() < 1e-9);
+        // One in three it's right, otherwise one of two is left for 1.5 guesses more.
+        assert!((score("slate") - 2.0).abs() < 1e-9);
+        assert_eq!(scored[2].0, "slate");
+    }
+
+    #[test]
+    fn probe_that_splits_nothing_comes_last() {
+        let possible = ["crate", "grate", "slate"];
+        let scored = expected(&["fuzzy", "crate", "grate", "slate"], &possible);
+
+        let (last, score) = scored.last().unwrap();
+        assert_eq!(last, "fuzzy");
+        assert!(scored[..3].iter().all(|&(_, other)| other < *score));
+    }
+}
@@ -0,0 +1,386 @@
+//! The feedback pattern of every guess against every answer, computed once and cached on disk.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::num::NonZero;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use crate::{feedback, pattern_index};
+
+// Start of every cached matrix.
+const MAGIC: &[u8; 4] = b"WHPM";
+
+// A single pattern, as small as the word length allows.
+trait Cell: Copy + Default + Send {
+    const WIDTH: u8;
+
+    fn from_pattern(pattern: usize) -> Self;
+    fn pattern(self) -> usize;
+    fn write(self, out: &mut impl Write) -> io::Result<()>;
+    fn decode(bytes: &[u8]) -> Self;
+}
+
+impl Cell for u8 {
+    const WIDTH: u8 = 1;
+
+    fn from_pattern(pattern: usize) -> Self {
+        pattern as u8
+    }
+
+    fn pattern(self) -> usize {
+        self as usize
+    }
+
+    fn write(self, out: &mut impl Write) -> io::Result<()> {
+        out.write_all(&[self])
+    }
+
+    fn decode(bytes: &[u8]) -> Self {
+        bytes[0]
+    }
+}
+
+impl Cell for u16 {
+    const WIDTH: u8 = 2;
+
+    fn from_pattern(pattern: usize) -> Self {
+        pattern as u16
+    }
+
+    fn pattern(self) -> usize {
+        self as usize
+    }
+
+    fn write(self, out: &mut impl Write) -> io::Result<()> {
+        out.write_all(&self.to_le_bytes())
+    }
+
+    fn decode(bytes: &[u8]) -> Self {
+        u16::from_le_bytes([bytes[0], bytes[1]])
+    }
+}
+
+impl Cell for u32 {
+    const WIDTH: u8 = 4;
+
+    fn from_pattern(pattern: usize) -> Self {
+        pattern as u32
+    }
+
+    fn pattern(self) -> usize {
+        self as usize
+    }
+
+    fn write(self, out: &mut impl Write) -> io::Result<()> {
+        out.write_all(&self.to_le_bytes())
+    }
+
+    fn decode(bytes: &[u8]) -> Self {
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+}
+
+#[derive(Debug)]
+enum Cells {
+    Narrow(Vec<u8>),
+    Medium(Vec<u16>),
+    Wide(Vec<u32>),
+}
+
+impl Cells {
+    fn get(&self, idx: usize) -> usize {
+        match self {
+            Cells::Narrow(cells) => cells[idx].pattern(),
+            Cells::Medium(cells) => cells[idx].pattern(),
+            Cells::Wide(cells) => cells[idx].pattern(),
+        }
+    }
+}
+
+/// The [`pattern_index`] of every guess against every answer.
+#[derive(Debug)]
+pub struct PatternMatrix {
+    guesses: HashMap<String, usize>,
+    answers: HashMap<String, usize>,
+    rows: usize,
+    columns: usize,
+    cells: Cells,
+}
+
+impl PatternMatrix {
+    /// Largest matrix that is computed, in bytes. The built-in list takes about 220 MB.
+    pub const MAX_BYTES: usize = 512 << 20;
+
+    /// Memory the matrix for these lists takes, and the size of its cache file.
+    pub fn bytes(guesses: &[&str], answers: &[&str]) -> usize {
+        let length = guesses.first().map_or(0, |w| w.chars().count());
+        let width = match length {
+            ..=5 => u8::WIDTH,
+            6..=10 => u16::WIDTH,
+            _ => u32::WIDTH,
+        };
+
+        (guesses.len() * answers.len()).saturating_mul(width as usize)
+    }
+
+    /// Computes the matrix on every available core, `None` if it would take more than
+    /// [`PatternMatrix::MAX_BYTES`]. All words must have the same length.
+    pub fn compute(guesses: &[&str], answers: &[&str]) -> Option<Self> {
+        if PatternMatrix::bytes(guesses, answers) > PatternMatrix::MAX_BYTES {
+            return None;
+        }
+
+        let length = guesses.first().map_or(0, |w| w.chars().count());
+        let guess_chars = chars(guesses);
+        let answer_chars = chars(answers);
+
+        // 3^5 patterns fit in a byte and 3^10 in two.
+        let cells = match length {
+            ..=5 => Cells::Narrow(compute(&guess_chars, &answer_chars)),
+            6..=10 => Cells::Medium(compute(&guess_chars, &answer_chars)),
+            _ => Cells::Wide(compute(&guess_chars, &answer_chars)),
+        };
+
+        Some(PatternMatrix {
+            guesses: positions(guesses),
+            answers: positions(answers),
+            rows: guesses.len(),
+            columns: answers.len(),
+            cells,
+        })
+    }
+
+    /// Reads the matrix for these lists from `dir`, or computes it and writes it there. The
+    /// matrix comes with the result of writing the cache, failing to write it only costs the
+    /// next start the time to compute it again. `None` if the matrix is too large, like for
+    /// [`PatternMatrix::compute`].
+    pub fn cached(
+        guesses: &[&str],
+        answers: &[&str],
+        dir: &Path,
+    ) -> Option<(Self, io::Result<()>)> {
+        if PatternMatrix::bytes(guesses, answers) > PatternMatrix::MAX_BYTES {
+            return None;
+        }
+
+        let path = cache_path(guesses, answers, dir);
+        if let Ok(matrix) = PatternMatrix::read(&path, guesses, answers) {
+            return Some((matrix, Ok(())));
+        }
+
+        let matrix = PatternMatrix::compute(guesses, answers)?;
+        let written = matrix
+            .write(&path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())));
+
+        Some((matrix, written))
+    }
+
+    /// Position of `w` among the guesses.
+    pub fn guess(&self, w: &str) -> Option<usize> {
+        self.guesses.get(w).copied()
+    }
+
+    /// Position of `w` among the answers.
+    pub fn answer(&self, w: &str) -> Option<usize> {
+        self.answers.get(w).copied()
+    }
+
+    /// The pattern of the guess and answer at these positions.
+    pub fn pattern(&self, guess: usize, answer: usize) -> usize {
+        self.cells.get(guess * self.columns + answer)
+    }
+
+    fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(MAGIC)?;
+        match &self.cells {
+            Cells::Narrow(cells) => write_cells(&mut out, cells, self)?,
+            Cells::Medium(cells) => write_cells(&mut out, cells, self)?,
+            Cells::Wide(cells) => write_cells(&mut out, cells, self)?,
+        }
+
+        out.flush()
+    }
+
+    fn read(path: &Path, guesses: &[&str], answers: &[&str]) -> io::Result<Self> {
+        let mut input = BufReader::new(File::open(path)?);
+
+        let mut header = [0; 13];
+        input.read_exact(&mut header)?;
+        let width = header[4];
+        let rows = u32::decode(&header[5..9]) as usize;
+        let columns = u32::decode(&header[9..13]) as usize;
+        if &header[..4] != MAGIC || rows != guesses.len() || columns != answers.len() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "stale pattern cache",
+            ));
+        }
+
+        let len = rows * columns;
+        let cells = match width {
+            u8::WIDTH => Cells::Narrow(read_cells(&mut input, len)?),
+            u16::WIDTH => Cells::Medium(read_cells(&mut input, len)?),
+            u32::WIDTH => Cells::Wide(read_cells(&mut input, len)?),
+            _ => return Err(io::Error::new(ErrorKind::InvalidData, "unknown cell width")),
+        };
+
+        Ok(PatternMatrix {
+            guesses: positions(guesses),
+            answers: positions(answers),
+            rows,
+            columns,
+            cells,
+        })
+    }
+}
+
+fn chars(words: &[&str]) -> Vec<Vec<char>> {
+    words.iter().map(|w| w.chars().collect()).collect()
+}
+
+fn positions(words: &[&str]) -> HashMap<String, usize> {
+    words
+        .iter()
+        .enumerate()
+        .map(|(idx, w)| (w.to_string(), idx))
+        .collect()
+}
+
+// Every thread fills a block of whole rows.
+fn compute<T: Cell>(guesses: &[Vec<char>], answers: &[Vec<char>]) -> Vec<T> {
+    let mut cells = vec![T::default(); guesses.len() * answers.len()];
+    if cells.is_empty() {
+        return cells;
+    }
+
+    let threads = thread::available_parallelism().map_or(1, NonZero::get);
+    let rows = guesses.len().div_ceil(threads);
+    thread::scope(|scope| {
+        for (guesses, cells) in guesses
+            .chunks(rows)
+            .zip(cells.chunks_mut(rows * answers.len()))
+        {
+            scope.spawn(move || {
+                for (guess, row) in guesses.iter().zip(cells.chunks_mut(answers.len())) {
+                    for (answer, cell) in answers.iter().zip(row) {
+                        *cell = T::from_pattern(pattern_index(&feedback(guess, answer)));
+                    }
+                }
+            });
+        }
+    });
+
+    cells
+}
+
+fn write_cells<T: Cell>(
+    out: &mut impl Write,
+    cells: &[T],
+    matrix: &PatternMatrix,
+) -> io::Result<()> {
+    let dimension = |len: usize| {
+        u32::try_from(len).map_err(|_| io::Error::new(ErrorKind::InvalidInput, "list too long"))
+    };
+
+    T::WIDTH.write(out)?;
+    dimension(matrix.rows)?.write(out)?;
+    dimension(matrix.columns)?.write(out)?;
+    for &cell in cells {
+        cell.write(out)?;
+    }
+
+    Ok(())
+}
+
+fn read_cells<T: Cell>(input: &mut impl Read, len: usize) -> io::Result<Vec<T>> {
+    let mut bytes = vec![0; len * T::WIDTH as usize];
+    input.read_exact(&mut bytes)?;
+
+    Ok(bytes
+        .chunks_exact(T::WIDTH as usize)
+        .map(T::decode)
+        .collect())
+}
+
+// FNV-1a, which unlike the standard hasher gives the same key on every run and platform.
+fn cache_path(guesses: &[&str], answers: &[&str], dir: &Path) -> PathBuf {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for list in [guesses, answers] {
+        for byte in list
+            .iter()
+            .flat_map(|w| w.bytes().chain([b'\n']))
+            .chain([0])
+        {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    dir.join(format!("patterns-{hash:016x}.bin"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &str) -> Vec<&str> {
+        list.split_whitespace().collect()
+    }
+
+    #[test]
+    fn cache_round_trip() {
+        let guesses = words("crane slate eerie geese llama");
+        let answers = words("abide these melee hello crust");
+        let dir = std::env::temp_dir().join(format!("wordle-helper-test-{}", std::process::id()));
+
+        let (computed, written) = PatternMatrix::cached(&guesses, &answers, &dir).unwrap();
+        written.unwrap();
+        let read = PatternMatrix::read(&cache_path(&guesses, &answers, &dir), &guesses, &answers);
+        fs::remove_dir_all(&dir).unwrap();
+        let read = read.unwrap();
+
+        for (g, guess) in guesses.iter().enumerate() {
+            for (a, answer) in answers.iter().enumerate() {
+                let guess_chars = guess.chars().collect::<Vec<_>>();
+                let answer_chars = answer.chars().collect::<Vec<_>>();
+                let expected = pattern_index(&feedback(&guess_chars, &answer_chars));
+
+                assert_eq!(computed.pattern(g, a), expected);
+                assert_eq!(read.pattern(g, a), expected);
+            }
+        }
+        assert_eq!(read.guess("llama"), Some(4));
+        assert_eq!(read.answer("crust"), Some(4));
+    }
+
+    #[test]
+    fn stale_cache_is_rejected() {
+        let guesses = words("crane slate");
+        let answers = words("abide these");
+        let dir = std::env::temp_dir().join(format!("wordle-helper-stale-{}", std::process::id()));
+        let path = cache_path(&guesses, &answers, &dir);
+
+        let matrix = PatternMatrix::compute(&guesses, &answers).unwrap();
+        matrix.write(&path).unwrap();
+        let stale = PatternMatrix::read(&path, &guesses, &answers[..1]);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(stale.is_err());
+    }
+
+    #[test]
+    fn refuses_huge_lists() {
+        let words = (0..30_000).map(|n| format!("{n:05}")).collect::<Vec<_>>();
+        let words = words.iter().map(String::as_str).collect::<Vec<_>>();
+        assert!(PatternMatrix::bytes(&words, &words) > PatternMatrix::MAX_BYTES);
+        assert!(PatternMatrix::compute(&words, &words).is_none());
+    }
+}
@@ -0,0 +1,310 @@
+//! Nerdle, where the answer is an equation like `12+35=47` instead of a word.
+//!
+//! The left side uses positive numbers and `+-*/` with the usual precedence, the right side is
+//! the number it evaluates to. Numbers have no leading zeros and a lone `0` is only allowed as
+//! the result. Divisions don't need to come out even as long as the result is a whole number.
+
+use std::ops::{Add, Div, Mul, Neg};
+
+/// Every character an equation may use.
+pub const ALPHABET: &str = "0123456789+-*/=";
+
+/// Equation lengths of Mini, classic and Maxi Nerdle.
+pub const LENGTHS: [usize; 3] = [6, 8, 10];
+
+/// Equation length of classic Nerdle.
+pub const DEFAULT_LENGTH: usize = 8;
+
+const OPERATORS: [char; 4] = ['+', '-', '*', '/'];
+
+// An exact fraction in lowest terms with a positive denominator.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Ratio {
+    num: i64,
+    den: i64,
+}
+
+impl Ratio {
+    const ZERO: Ratio = Ratio { num: 0, den: 1 };
+
+    fn new(num: i64, den: i64) -> Self {
+        let divisor = gcd(num, den) * den.signum();
+        Ratio {
+            num: num / divisor,
+            den: den / divisor,
+        }
+    }
+
+    fn whole(n: i64) -> Self {
+        Ratio { num: n, den: 1 }
+    }
+
+    fn as_whole(self) -> Option<i64> {
+        (self.den == 1).then_some(self.num)
+    }
+}
+
+impl Add for Ratio {
+    type Output = Ratio;
+
+    fn add(self, other: Ratio) -> Ratio {
+        Ratio::new(
+            self.num * other.den + other.num * self.den,
+            self.den * other.den,
+        )
+    }
+}
+
+impl Mul for Ratio {
+    type Output = Ratio;
+
+    fn mul(self, other: Ratio) -> Ratio {
+        Ratio::new(self.num * other.num, self.den * other.den)
+    }
+}
+
+// Only called with a non-zero divisor.
+impl Div for Ratio {
+    type Output = Ratio;
+
+    fn div(self, other: Ratio) -> Ratio {
+        Ratio::new(self.num * other.den, self.den * other.num)
+    }
+}
+
+impl Neg for Ratio {
+    type Output = Ratio;
+
+    fn neg(self) -> Ratio {
+        Ratio {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a.max(1)
+}
+
+// The left side evaluated up to a number: the terms already added up, the term being
+// multiplied out and the operator that combines it with the next number.
+#[derive(Clone, Copy)]
+struct Partial {
+    sum: Ratio,
+    term: Ratio,
+    op: char,
+}
+
+impl Partial {
+    const START: Partial = Partial {
+        sum: Ratio::ZERO,
+        term: Ratio::ZERO,
+        op: '+',
+    };
+
+    fn push(self, n: i64, op: char) -> Partial {
+        let n = Ratio::whole(n);
+        let (sum, term) = match self.op {
+            '+' => (self.sum + self.term, n),
+            '-' => (self.sum + self.term, -n),
+            '*' => (self.sum, self.term * n),
+            _ => (self.sum, self.term / n),
+        };
+
+        Partial { sum, term, op }
+    }
+
+    fn value(self, last: i64) -> Ratio {
+        let Partial { sum, term, .. } = self.push(last, '+');
+        sum + term
+    }
+
+    // The last number that makes the left side evaluate to `result`, if there is a whole one.
+    fn solve(self, result: i64) -> Option<i64> {
+        let result = Ratio::whole(result);
+        let x = match self.op {
+            '+' => result + -(self.sum + self.term),
+            '-' => self.sum + self.term + -result,
+            '*' => (result + -self.sum) / self.term,
+            _ if result == self.sum => return None,
+            _ => self.term / (result + -self.sum),
+        };
+
+        x.as_whole()
+    }
+}
+
+// Numbers written with exactly `digits` digits, no leading zero and not a lone `0`.
+fn numbers(digits: usize) -> std::ops::RangeInclusive<i64> {
+    let digits = digits as u32;
+    10i64.pow(digits - 1)..=10i64.pow(digits) - 1
+}
+
+// Possible results with exactly `digits` digits, where `0` is allowed.
+fn results(digits: usize) -> std::ops::RangeInclusive<i64> {
+    match digits {
+        1 => 0..=9,
+        _ => numbers(digits),
+    }
+}
+
+fn digits(n: i64) -> usize {
+    n.to_string().len()
+}
+
+/// Every valid equation of `length` characters, sorted.
+pub fn equations(length: usize) -> Vec<String> {
+    let mut found = Vec::new();
+
+    // The shortest left side is `1+2`.
+    for result_digits in 1..=length.saturating_sub(4) {
+        let left = length - 1 - result_digits;
+        let mut prefix = String::new();
+        extend(&mut prefix, Partial::START, left, result_digits, &mut found);
+    }
+
+    found.sort_unstable();
+    found
+}
+
+// Adds the numbers and operators of the left side one at a time. The last number is
+// whichever makes the result come out right, so it is solved for instead of tried.
+fn extend(
+    prefix: &mut String,
+    partial: Partial,
+    left: usize,
+    result_digits: usize,
+    found: &mut Vec<String>,
+) {
+    if !prefix.is_empty() {
+        finish(prefix, partial, left, result_digits, found);
+    }
+
+    // The number, an operator and at least one more digit.
+    for number_digits in 1..left.saturating_sub(1) {
+        for n in numbers(number_digits) {
+            for op in OPERATORS {
+                let len = prefix.len();
+                prefix.push_str(&n.to_string());
+                prefix.push(op);
+                extend(
+                    prefix,
+                    partial.push(n, op),
+                    left - number_digits - 1,
+                    result_digits,
+                    found,
+                );
+                prefix.truncate(len);
+            }
+        }
+    }
+}
+
+// Every last number of `last_digits` digits that completes the equation. Tries whichever of
+// the last numbers and the results are fewer.
+fn finish(
+    prefix: &str,
+    partial: Partial,
+    last_digits: usize,
+    result_digits: usize,
+    found: &mut Vec<String>,
+) {
+    let mut push = |last: i64, result: i64| found.push(format!("{prefix}{last}={result}"));
+    let lasts = numbers(last_digits);
+    let results = results(result_digits);
+
+    if lasts.end() - lasts.start() <= results.end() - results.start() {
+        for last in lasts {
+            if let Some(result) = partial.value(last).as_whole()
+                && results.contains(&result)
+            {
+                push(last, result);
+            }
+        }
+    } else {
+        for result in results {
+            if let Some(last) = partial.solve(result)
+                && lasts.contains(&last)
+            {
+                push(last, result);
+            }
+        }
+    }
+}
+
+/// Whether `equation` is a valid equation, i.e. one of [`equations`] of its length.
+pub fn is_valid(equation: &str) -> bool {
+    let Some((left, right)) = equation.split_once('=') else {
+        return false;
+    };
+    let Ok(result) = right.parse::<i64>() else {
+        return false;
+    };
+    if !right.bytes().all(|b| b.is_ascii_digit()) || digits(result) != right.len() {
+        return false;
+    }
+
+    let mut partial = Partial::START;
+    let mut operators = 0;
+    let mut rest = left;
+    loop {
+        let end = rest.find(OPERATORS).unwrap_or(rest.len());
+        let number = &rest[..end];
+        let Ok(n) = number.parse::<i64>() else {
+            return false;
+        };
+        if !number.bytes().all(|b| b.is_ascii_digit()) || n == 0 || digits(n) != number.len() {
+            return false;
+        }
+
+        match rest[end..].chars().next() {
+            Some(op) => {
+                partial = partial.push(n, op);
+                operators += 1;
+                rest = &rest[end + 1..];
+            }
+            None => {
+                return operators > 0 && partial.value(n) == Ratio::whole(result);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic_equation_count() {
+        let equations = equations(8);
+        assert_eq!(equations.len(), 17723);
+        assert!(equations.iter().all(|equation| is_valid(equation)));
+    }
+
+    #[test]
+    fn mini_equations() {
+        let equations = equations(6);
+        assert_eq!(equations.len(), 206);
+        assert!(equations.contains(&"4*3=12".to_string()));
+        assert!(equations.contains(&"10-9=1".to_string()));
+    }
+
+    #[test]
+    fn validity() {
+        assert!(is_valid("12+35=47"));
+        assert!(is_valid("9/3*2=6"));
+        assert!(is_valid("3-2-1=0"));
+        assert!(!is_valid("12+35=48"));
+        assert!(!is_valid("01+2=3"));
+        assert!(!is_valid("0+3=3"));
+        assert!(!is_valid("3=3"));
+        assert!(!is_valid("1+2=03"));
+        assert!(!is_valid("1+2+3"));
+    }
+}
@@ -0,0 +1,99 @@
+//! How likely words are to be the answer, from how common they are.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::word_list::{Accents, fold};
+
+/// Word frequencies used as the prior probability of every candidate.
+#[derive(Clone, Debug, Default)]
+pub struct Prior {
+    counts: HashMap<String, f64>,
+    // Given to words missing from the list, so they stay possible but unlikely.
+    unknown: f64,
+}
+
+impl Prior {
+    /// Parses one word and its count or probability per line, separated by whitespace or a
+    /// comma. Blank lines and lines starting with `#` are skipped. Words are [`fold`]ed like the
+    /// word lists, so words that only differ in stripped accents share their counts.
+    pub fn parse(text: &str, accents: Accents) -> Result<Self, String> {
+        let mut counts = HashMap::new();
+
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line
+                .split(|ch: char| ch == ',' || ch.is_whitespace())
+                .filter(|field| !field.is_empty());
+            let (Some(w), Some(count), None) = (fields.next(), fields.next(), fields.next()) else {
+                return Err(format!("line {}: expected WORD COUNT", idx + 1));
+            };
+            let count = count
+                .parse::<f64>()
+                .ok()
+                .filter(|count| count.is_finite() && *count >= 0.0)
+                .ok_or_else(|| format!("line {}: \"{count}\" isn't a count", idx + 1))?;
+
+            *counts.entry(fold(w, accents)).or_default() += count;
+        }
+
+        let smallest = counts
+            .values()
+            .copied()
+            .filter(|&count| count > 0.0)
+            .fold(f64::INFINITY, f64::min);
+        let unknown = if smallest.is_finite() {
+            smallest / 2.0
+        } else {
+            1.0
+        };
+
+        Ok(Prior { counts, unknown })
+    }
+
+    /// Reads and [`Prior::parse`]s the frequency file at `path`.
+    pub fn read(path: &Path, accents: Accents) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        Prior::parse(&text, accents)
+    }
+
+    /// Number of words with a frequency.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The probability of each of `words` being the answer, summing to 1.
+    pub fn weights(&self, words: &[&str]) -> Vec<f64> {
+        let counts = words
+            .iter()
+            .map(|&w| self.counts.get(w).copied().unwrap_or(self.unknown))
+            .collect::<Vec<_>>();
+
+        normalized(counts)
+    }
+}
+
+/// Scales `weights` to sum to 1, or makes them uniform if they are all zero.
+pub fn normalized(mut weights: Vec<f64>) -> Vec<f64> {
+    let total = weights.iter().sum::<f64>();
+    let uniform = 1.0 / weights.len().max(1) as f64;
+
+    for weight in &mut weights {
+        *weight = if total > 0.0 {
+            *weight / total
+        } else {
+            uniform
+        };
+    }
+
+    weights
+}
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use crate::prior::normalized;
+use crate::strategy::order;
+use crate::{Index, PatternMatrix, Prior, ScoreContext, Strategy, Word, WorstCase};
+
+/// Most guesses ranked per list. Longer lists, like the equations of Maxi Nerdle, are thinned
+/// out to an evenly spaced sample of this many.
+pub const MAX_RANKED: usize = 20_000;
+
+// At most `MAX_RANKED` evenly spaced `items`, all of them if there are fewer.
+pub(crate) fn spread<T: Copy>(items: &[T]) -> Vec<T> {
+    if items.len() <= MAX_RANKED {
+        return items.to_vec();
+    }
+
+    (0..MAX_RANKED)
+        .map(|idx| items[idx * items.len() / MAX_RANKED])
+        .collect()
+}
+
+/// Number of different letters in `w`, the tie breaker for equal scores.
+pub fn distinct_letters(w: &str) -> usize {
+    let mut w = w.chars().collect::<Vec<_>>();
+    w.sort_unstable();
+    w.dedup();
+    w.len()
+}
+
+/// A ranked guess.
+#[derive(Clone, Debug)]
+pub struct Scored {
+    /// Position of the guess in the word list it was ranked from.
+    pub word: usize,
+    /// Score given by the ranking strategy.
+    pub score: f64,
+    /// Most candidates that can be left over after this guess, if the strategy tells.
+    pub worst_case: Option<WorstCase>,
+    /// Estimated probability of this guess being the answer.
+    pub probability: f64,
+}
+
+// Ranks `guesses` like `Strategy::rank` and adds the worst case and the probability to every
+// guess. `positions` are the positions of `guesses` in their word list.
+fn scored(
+    guesses: &[&str],
+    positions: &[usize],
+    possible: &[&str],
+    probabilities: &HashMap<&str, f64>,
+    strategy: &dyn Strategy,
+    context: ScoreContext,
+) -> Vec<Scored> {
+    let (scores, worst_cases): (Vec<_>, Vec<_>) = strategy
+        .scores_with_worst_cases(guesses, possible, context)
+        .into_iter()
+        .unzip();
+
+    order(guesses, &scores, possible, context.weights, |a, b| {
+        strategy.compare(a, b)
+    })
+    .into_iter()
+    .map(|idx| Scored {
+        word: positions[idx],
+        score: scores[idx],
+        worst_case: worst_cases[idx],
+        probability: probabilities.get(guesses[idx]).copied().unwrap_or(0.0),
+    })
+    .collect()
+}
+
+/// The remaining candidates and the best guesses for the current constraints. Words are kept as
+/// positions in the lists they were ranked from, look them up with [`Index::words`].
+#[derive(Default)]
+pub struct Suggestions {
+    /// Positions of every answer that is still possible, ascending.
+    pub possible: Vec<usize>,
+    /// `possible` ranked by how much they reveal, best first. Only a sample of [`MAX_RANKED`]
+    /// if there are more.
+    pub ranked: Vec<Scored>,
+    /// Every allowed guess ranked by how much it reveals about `possible`, best first, or a
+    /// sample like for `ranked`. Words are positions among the allowed words.
+    pub probes: Vec<Scored>,
+    /// Position among the allowed words of the best probe if it can't be the answer but still
+    /// beats every candidate. Always `None` without candidates.
+    pub better_probe: Option<usize>,
+}
+
+impl Suggestions {
+    /// Candidates only come from `answers`, probes may be any `allowed` word. Both are ranked
+    /// with `strategy`, which weighs the candidates by `prior` and looks up `patterns` if given.
+    pub fn new(
+        answers: &Index,
+        allowed: &Index,
+        word: &Word,
+        hard_mode: bool,
+        strategy: &dyn Strategy,
+        prior: Option<&Prior>,
+        patterns: Option<&PatternMatrix>,
+    ) -> Self {
+        let probes = if hard_mode {
+            allowed.hard_mode_matches(word)
+        } else {
+            allowed.matches(&Word::new(word.len()))
+        };
+
+        Suggestions::from_possible(
+            answers,
+            answers.matches(word).iter().collect(),
+            allowed,
+            &probes.iter().collect::<Vec<_>>(),
+            strategy,
+            prior,
+            patterns,
+        )
+    }
+
+    /// Ranks the candidates at the `possible` positions of `answers`, narrowed down some other
+    /// way, e.g. by [`fibble::possible`](crate::fibble::possible), and the `allowed` words at the
+    /// `probes` positions.
+    pub fn from_possible(
+        answers: &Index,
+        possible: Vec<usize>,
+        allowed: &Index,
+        probes: &[usize],
+        strategy: &dyn Strategy,
+        prior: Option<&Prior>,
+        patterns: Option<&PatternMatrix>,
+    ) -> Self {
+        let possible_words = answers.words_at(&possible);
+        let candidates = spread(&possible);
+        let probes = spread(probes);
+
+        let weights = prior.map(|prior| prior.weights(&possible_words));
+        let probabilities = possible_words
+            .iter()
+            .copied()
+            .zip(
+                weights
+                    .clone()
+                    .unwrap_or_else(|| normalized(vec![1.0; possible.len()])),
+            )
+            .collect::<HashMap<_, _>>();
+
+        let context = ScoreContext {
+            weights: weights.as_deref(),
+            patterns,
+        };
+        let scored = |guesses: &[&str], positions: &[usize]| {
+            scored(
+                guesses,
+                positions,
+                &possible_words,
+                &probabilities,
+                strategy,
+                context,
+            )
+        };
+
+        let ranked = scored(&answers.words_at(&candidates), &candidates);
+        let probes = scored(&allowed.words_at(&probes), &probes);
+
+        let better_probe = probes.first().and_then(|probe| {
+            // Without candidates there is nothing to beat, and nothing left to find either.
+            let beats_candidates = ranked
+                .first()
+                .is_some_and(|best| strategy.compare(probe.score, best.score).is_lt());
+            let is_candidate = probabilities.contains_key(allowed.words()[probe.word].as_str());
+
+            (beats_candidates && !is_candidate).then_some(probe.word)
+        });
+
+        Suggestions {
+            possible,
+            ranked,
+            probes,
+            better_probe,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Entropy, Guess};
+
+    fn index(list: &str) -> Index {
+        Index::new(list.split_whitespace().map(String::from).collect())
+    }
+
+    #[test]
+    fn no_better_probe_without_candidates() {
+        let answers = index("crane slate");
+        let allowed = index("crane slate tares");
+        let word = Word::from_guesses(5, &[Guess::parse("moist:ggggg").unwrap()]);
+        let suggestions = Suggestions::new(&answers, &allowed, &word, false, &Entropy, None, None);
+
+        assert!(suggestions.possible.is_empty());
+        assert!(suggestions.better_probe.is_none());
+    }
+
+    #[test]
+    fn long_lists_are_sampled_evenly() {
+        let items = (0..MAX_RANKED * 3 + 1).collect::<Vec<_>>();
+        let sample = spread(&items);
+
+        assert_eq!(sample.len(), MAX_RANKED);
+        assert_eq!(sample[0], 0);
+        assert!(sample.windows(2).all(|pair| pair[1] - pair[0] == 3));
+        assert_eq!(spread(&items[..10]), &items[..10]);
+    }
+}
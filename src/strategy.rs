@@ -0,0 +1,630 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::prior::normalized;
+use crate::{PatternMatrix, distinct_letters, pattern};
+
+// Candidate lists larger than this are scored against an evenly spaced sample.
+const SAMPLE_SIZE: usize = 1000;
+
+/// Every built-in strategy, the default first.
+pub const STRATEGIES: [&dyn Strategy; 6] = [
+    &Entropy,
+    &Minimax,
+    &ExpectedSize,
+    &LetterFrequency,
+    &PositionalFrequency,
+    &DistinctLetters,
+];
+
+/// Looks up a built-in strategy by its [`Strategy::name`].
+pub fn strategy(name: &str) -> Option<&'static dyn Strategy> {
+    STRATEGIES
+        .into_iter()
+        .find(|strategy| strategy.name() == name)
+}
+
+/// What a strategy may know besides the guesses and the possible answers.
+#[derive(Clone, Copy, Default)]
+pub struct ScoreContext<'a> {
+    /// Probabilities of the possible answers being the answer, all equally likely if `None`.
+    pub weights: Option<&'a [f64]>,
+    /// Precomputed patterns, used for every guess and answer they cover. Looking them up is
+    /// cheap, so if they cover every possible answer none of them are left out as a sample.
+    pub patterns: Option<&'a PatternMatrix>,
+}
+
+/// The most candidates a guess can leave over.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WorstCase {
+    /// Counted over every candidate.
+    Exact(usize),
+    /// Scaled up from a sample of the candidates, so it can be off either way.
+    Estimate(usize),
+}
+
+impl WorstCase {
+    pub fn count(self) -> usize {
+        match self {
+            WorstCase::Exact(count) | WorstCase::Estimate(count) => count,
+        }
+    }
+}
+
+/// A way of scoring guesses against the answers that are still possible.
+pub trait Strategy: Sync {
+    /// Short name used on the command line.
+    fn name(&self) -> &'static str;
+
+    /// Name shown in the UI.
+    fn label(&self) -> &'static str;
+
+    /// Whether lower scores are better, e.g. for the number of words left over.
+    fn lower_is_better(&self) -> bool {
+        false
+    }
+
+    /// Whether scoring looks up [`ScoreContext::patterns`], so precomputing them pays off.
+    fn uses_patterns(&self) -> bool {
+        false
+    }
+
+    /// Scores every word in `guesses` against the remaining `possible` answers.
+    fn scores(&self, guesses: &[&str], possible: &[&str], context: ScoreContext) -> Vec<f64>;
+
+    /// Like [`Strategy::scores`], together with the worst case of every guess if working out
+    /// the score also tells how many candidates it can leave over.
+    fn scores_with_worst_cases(
+        &self,
+        guesses: &[&str],
+        possible: &[&str],
+        context: ScoreContext,
+    ) -> Vec<(f64, Option<WorstCase>)> {
+        self.scores(guesses, possible, context)
+            .into_iter()
+            .map(|score| (score, None))
+            .collect()
+    }
+
+    /// Orders the better of two scores first.
+    fn compare(&self, a: f64, b: f64) -> Ordering {
+        if self.lower_is_better() {
+            a.total_cmp(&b)
+        } else {
+            b.total_cmp(&a)
+        }
+    }
+
+    /// Ranks `guesses` best first, as their positions in `guesses` and their scores. Ties go to
+    /// the more likely answer, then to the one with more distinct letters.
+    fn rank(
+        &self,
+        guesses: &[&str],
+        possible: &[&str],
+        context: ScoreContext,
+    ) -> Vec<(usize, f64)> {
+        let scores = self.scores(guesses, possible, context);
+
+        order(guesses, &scores, possible, context.weights, |a, b| {
+            self.compare(a, b)
+        })
+        .into_iter()
+        .map(|idx| (idx, scores[idx]))
+        .collect()
+    }
+}
+
+/// Positions of `guesses` sorted by their `scores` with `compare`, like [`Strategy::rank`]
+/// does. `weights` are the probabilities of the `possible` answers.
+pub(crate) fn order(
+    guesses: &[&str],
+    scores: &[f64],
+    possible: &[&str],
+    weights: Option<&[f64]>,
+    compare: impl Fn(f64, f64) -> Ordering,
+) -> Vec<usize> {
+    let likelihood = match weights {
+        Some(weights) => possible
+            .iter()
+            .copied()
+            .zip(weights.iter().copied())
+            .collect(),
+        None => HashMap::new(),
+    };
+    let likelihood = |idx: usize| likelihood.get(guesses[idx]).copied().unwrap_or(0.0);
+
+    let mut order = (0..guesses.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| {
+        compare(scores[a], scores[b])
+            .then_with(|| likelihood(b).total_cmp(&likelihood(a)))
+            .then_with(|| distinct_letters(guesses[b]).cmp(&distinct_letters(guesses[a])))
+    });
+
+    order
+}
+
+fn without_worst_cases(scored: Vec<(f64, Option<WorstCase>)>) -> Vec<f64> {
+    scored.into_iter().map(|(score, _)| score).collect()
+}
+
+// `scores` of strategies that don't look at the feedback, with the worst cases counted from it.
+fn with_worst_cases(
+    scores: Vec<f64>,
+    guesses: &[&str],
+    possible: &[&str],
+    context: ScoreContext,
+) -> Vec<(f64, Option<WorstCase>)> {
+    let sample = Sample::new(possible, context, SAMPLE_SIZE);
+
+    scores
+        .into_iter()
+        .zip(sample.scores(guesses, |_| 0.0))
+        .map(|(score, (_, worst_case))| (score, worst_case))
+        .collect()
+}
+
+// The candidates guesses are scored against. Lists larger than `size` are thinned out to an
+// evenly spaced sample unless the matrix covers all of them, and `scale` converts sample counts
+// back to counts in the full list. The weights of the sampled answers sum to 1. Patterns are
+// looked up in the matrix if it covers every sampled answer.
+pub(crate) struct Sample<'a> {
+    answers: Vec<Vec<char>>,
+    weights: Vec<f64>,
+    scale: f64,
+    matrix: Option<(&'a PatternMatrix, Vec<usize>)>,
+}
+
+// The groups of a single guess, kept between guesses to save allocations. `by_pattern` is all
+// zeros again once the groups are collected.
+struct Groups {
+    by_pattern: Vec<(usize, f64)>,
+    seen: Vec<usize>,
+    groups: Vec<(usize, f64)>,
+}
+
+impl<'a> Sample<'a> {
+    pub(crate) fn new(possible: &[&str], context: ScoreContext<'a>, size: usize) -> Self {
+        let columns = |step: usize| {
+            let matrix = context.patterns?;
+            possible
+                .iter()
+                .step_by(step)
+                .map(|w| matrix.answer(w))
+                .collect::<Option<Vec<_>>>()
+                .map(|columns| (matrix, columns))
+        };
+        let (step, matrix) = match columns(1) {
+            Some(matrix) => (1, Some(matrix)),
+            None => {
+                let step = possible.len().div_ceil(size).max(1);
+                (step, columns(step))
+            }
+        };
+
+        let answers = possible
+            .iter()
+            .step_by(step)
+            .map(|w| w.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let weights = match context.weights {
+            Some(weights) => weights.iter().step_by(step).copied().collect(),
+            None => vec![1.0; answers.len()],
+        };
+        let scale = possible.len() as f64 / answers.len().max(1) as f64;
+
+        Sample {
+            answers,
+            weights: normalized(weights),
+            scale,
+            matrix,
+        }
+    }
+
+    // Size and probability of every group of sampled answers that give the same feedback for
+    // `guess`.
+    fn partition<'g>(&self, guess: &str, groups: &'g mut Groups) -> &'g [(usize, f64)] {
+        let Groups {
+            by_pattern,
+            seen,
+            groups,
+        } = groups;
+        let mut add = |pattern: usize, idx: usize| {
+            let group = &mut by_pattern[pattern];
+            if group.0 == 0 {
+                seen.push(pattern);
+            }
+            group.0 += 1;
+            group.1 += self.weights[idx];
+        };
+
+        let row = self
+            .matrix
+            .as_ref()
+            .and_then(|(matrix, _)| matrix.guess(guess));
+        match (&self.matrix, row) {
+            (Some((matrix, columns)), Some(row)) => {
+                for (idx, &column) in columns.iter().enumerate() {
+                    add(matrix.pattern(row, column), idx);
+                }
+            }
+            _ => {
+                let guess = guess.chars().collect::<Vec<_>>();
+                for (idx, answer) in self.answers.iter().enumerate() {
+                    add(pattern(&guess, answer), idx);
+                }
+            }
+        }
+
+        groups.clear();
+        groups.extend(
+            seen.drain(..)
+                .map(|pattern| std::mem::take(&mut by_pattern[pattern])),
+        );
+
+        groups
+    }
+
+    // Scores every guess from its groups. The largest group is its worst case.
+    pub(crate) fn scores(
+        &self,
+        guesses: &[&str],
+        score: impl Fn(&[(usize, f64)]) -> f64,
+    ) -> Vec<(f64, Option<WorstCase>)> {
+        let length = self.answers.first().map_or(0, Vec::len);
+        let mut groups = Groups {
+            by_pattern: vec![(0, 0.0); 3usize.pow(length as u32)],
+            seen: Vec::new(),
+            groups: Vec::new(),
+        };
+
+        guesses
+            .iter()
+            .map(|guess| {
+                let groups = self.partition(guess, &mut groups);
+                let largest = groups.iter().map(|&(size, _)| size).max().unwrap_or(0);
+                let worst_case = if self.scale > 1.0 {
+                    WorstCase::Estimate((largest as f64 * self.scale).round() as usize)
+                } else {
+                    WorstCase::Exact(largest)
+                };
+
+                (score(groups), Some(worst_case))
+            })
+            .collect()
+    }
+}
+
+/// Shannon entropy in bits of the feedback patterns a guess splits the candidates into.
+pub struct Entropy;
+
+impl Strategy for Entropy {
+    fn name(&self) -> &'static str {
+        "entropy"
+    }
+
+    fn label(&self) -> &'static str {
+        "Entropy (bits)"
+    }
+
+    fn uses_patterns(&self) -> bool {
+        true
+    }
+
+    fn scores(&self, guesses: &[&str], possible: &[&str], context: ScoreContext) -> Vec<f64> {
+        without_worst_cases(self.scores_with_worst_cases(guesses, possible, context))
+    }
+
+    fn scores_with_worst_cases(
+        &self,
+        guesses: &[&str],
+        possible: &[&str],
+        context: ScoreContext,
+    ) -> Vec<(f64, Option<WorstCase>)> {
+        let sample = Sample::new(possible, context, SAMPLE_SIZE);
+
+        sample.scores(guesses, |groups| {
+            groups
+                .iter()
+                .filter(|&&(_, p)| p > 0.0)
+                .map(|&(_, p)| -p * p.log2())
+                .sum()
+        })
+    }
+}
+
+/// Number of candidates left in the worst case.
+pub struct Minimax;
+
+impl Strategy for Minimax {
+    fn name(&self) -> &'static str {
+        "minimax"
+    }
+
+    fn label(&self) -> &'static str {
+        "Minimax (worst case left)"
+    }
+
+    fn uses_patterns(&self) -> bool {
+        true
+    }
+
+    fn lower_is_better(&self) -> bool {
+        true
+    }
+
+    fn scores(&self, guesses: &[&str], possible: &[&str], context: ScoreContext) -> Vec<f64> {
+        without_worst_cases(self.scores_with_worst_cases(guesses, possible, context))
+    }
+
+    fn scores_with_worst_cases(
+        &self,
+        guesses: &[&str],
+        possible: &[&str],
+        context: ScoreContext,
+    ) -> Vec<(f64, Option<WorstCase>)> {
+        let sample = Sample::new(possible, context, SAMPLE_SIZE);
+
+        sample.scores(guesses, |groups| {
+            groups.iter().map(|&(size, _)| size).max().unwrap_or(0) as f64 * sample.scale
+        })
+    }
+}
+
+/// Number of candidates left on average, with groups as likely as the answers in them.
+pub struct ExpectedSize;
+
+impl Strategy for ExpectedSize {
+    fn name(&self) -> &'static str {
+        "expected"
+    }
+
+    fn label(&self) -> &'static str {
+        "Expected words left"
+    }
+
+    fn uses_patterns(&self) -> bool {
+        true
+    }
+
+    fn lower_is_better(&self) -> bool {
+        true
+    }
+
+    fn scores(&self, guesses: &[&str], possible: &[&str], context: ScoreContext) -> Vec<f64> {
+        without_worst_cases(self.scores_with_worst_cases(guesses, possible, context))
+    }
+
+    fn scores_with_worst_cases(
+        &self,
+        guesses: &[&str],
+        possible: &[&str],
+        context: ScoreContext,
+    ) -> Vec<(f64, Option<WorstCase>)> {
+        let sample = Sample::new(possible, context, SAMPLE_SIZE);
+
+        sample.scores(guesses, |groups| {
+            let expected = groups.iter().map(|&(size, p)| size as f64 * p).sum::<f64>();
+            expected * sample.scale
+        })
+    }
+}
+
+/// Average number of distinct guessed letters a candidate contains.
+pub struct LetterFrequency;
+
+impl Strategy for LetterFrequency {
+    fn name(&self) -> &'static str {
+        "frequency"
+    }
+
+    fn label(&self) -> &'static str {
+        "Letter frequency"
+    }
+
+    fn scores(&self, guesses: &[&str], possible: &[&str], _context: ScoreContext) -> Vec<f64> {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for w in possible {
+            let mut letters = w.chars().collect::<Vec<_>>();
+            letters.sort_unstable();
+            letters.dedup();
+            for letter in letters {
+                *counts.entry(letter).or_default() += 1;
+            }
+        }
+
+        let total = possible.len().max(1) as f64;
+        guesses
+            .iter()
+            .map(|guess| {
+                let mut letters = guess.chars().collect::<Vec<_>>();
+                letters.sort_unstable();
+                letters.dedup();
+
+                let found = letters
+                    .iter()
+                    .map(|letter| counts.get(letter).copied().unwrap_or(0))
+                    .sum::<usize>();
+                found as f64 / total
+            })
+            .collect()
+    }
+
+    fn scores_with_worst_cases(
+        &self,
+        guesses: &[&str],
+        possible: &[&str],
+        context: ScoreContext,
+    ) -> Vec<(f64, Option<WorstCase>)> {
+        let scores = self.scores(guesses, possible, context);
+        with_worst_cases(scores, guesses, possible, context)
+    }
+}
+
+/// Average number of green tiles a guess gets.
+pub struct PositionalFrequency;
+
+impl Strategy for PositionalFrequency {
+    fn name(&self) -> &'static str {
+        "positional"
+    }
+
+    fn label(&self) -> &'static str {
+        "Positional letter frequency"
+    }
+
+    fn scores(&self, guesses: &[&str], possible: &[&str], _context: ScoreContext) -> Vec<f64> {
+        let mut counts: HashMap<(usize, char), usize> = HashMap::new();
+        for w in possible {
+            for letter in w.chars().enumerate() {
+                *counts.entry(letter).or_default() += 1;
+            }
+        }
+
+        let total = possible.len().max(1) as f64;
+        guesses
+            .iter()
+            .map(|guess| {
+                let greens = guess
+                    .chars()
+                    .enumerate()
+                    .map(|letter| counts.get(&letter).copied().unwrap_or(0))
+                    .sum::<usize>();
+                greens as f64 / total
+            })
+            .collect()
+    }
+
+    fn scores_with_worst_cases(
+        &self,
+        guesses: &[&str],
+        possible: &[&str],
+        context: ScoreContext,
+    ) -> Vec<(f64, Option<WorstCase>)> {
+        let scores = self.scores(guesses, possible, context);
+        with_worst_cases(scores, guesses, possible, context)
+    }
+}
+
+/// Number of distinct letters in the guess, ignoring the candidates entirely.
+pub struct DistinctLetters;
+
+impl Strategy for DistinctLetters {
+    fn name(&self) -> &'static str {
+        "distinct"
+    }
+
+    fn label(&self) -> &'static str {
+        "Distinct letters"
+    }
+
+    fn scores(&self, guesses: &[&str], _possible: &[&str], _context: ScoreContext) -> Vec<f64> {
+        guesses
+            .iter()
+            .map(|guess| distinct_letters(guess) as f64)
+            .collect()
+    }
+
+    fn scores_with_worst_cases(
+        &self,
+        guesses: &[&str],
+        possible: &[&str],
+        context: ScoreContext,
+    ) -> Vec<(f64, Option<WorstCase>)> {
+        let scores = self.scores(guesses, possible, context);
+        with_worst_cases(scores, guesses, possible, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{feedback, pattern_index, word_list};
+
+    // The largest group of `possible` that `guess` can leave, counted one by one.
+    fn largest_group(guess: &str, possible: &[&str]) -> usize {
+        let guess = guess.chars().collect::<Vec<_>>();
+        let mut sizes: HashMap<usize, usize> = HashMap::new();
+        for answer in possible {
+            let answer = answer.chars().collect::<Vec<_>>();
+            *sizes
+                .entry(pattern_index(&feedback(&guess, &answer)))
+                .or_default() += 1;
+        }
+
+        sizes.into_values().max().unwrap_or(0)
+    }
+
+    #[test]
+    fn worst_cases_are_exact_unless_sampled() {
+        let answers = word_list::default_answers();
+        let possible = answers
+            .iter()
+            .step_by(10)
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        assert!(possible.len() > SAMPLE_SIZE);
+        let guesses = ["tares", "crane", "fuzzy"];
+
+        let sampled = Minimax.scores_with_worst_cases(&guesses, &possible, ScoreContext::default());
+        assert!(
+            sampled
+                .iter()
+                .all(|(_, worst_case)| matches!(worst_case, Some(WorstCase::Estimate(_))))
+        );
+
+        let matrix = PatternMatrix::compute(&possible, &possible).unwrap();
+        let context = ScoreContext {
+            weights: None,
+            patterns: Some(&matrix),
+        };
+        for strategy in [&Entropy as &dyn Strategy, &Minimax, &ExpectedSize] {
+            let exact = strategy.scores_with_worst_cases(&guesses, &possible, context);
+            for (guess, (_, worst_case)) in guesses.iter().zip(exact) {
+                let expected = largest_group(guess, &possible);
+                assert_eq!(worst_case, Some(WorstCase::Exact(expected)));
+            }
+        }
+
+        let small = &possible[..50];
+        for (guess, (score, worst_case)) in guesses.iter().zip(Minimax.scores_with_worst_cases(
+            &guesses,
+            small,
+            ScoreContext::default(),
+        )) {
+            assert_eq!(
+                worst_case,
+                Some(WorstCase::Exact(largest_group(guess, small)))
+            );
+            assert_eq!(score, largest_group(guess, small) as f64);
+        }
+    }
+
+    #[test]
+    fn letter_strategies_count_worst_cases() {
+        let guesses = ["crane", "geese"];
+        let possible = ["abide", "crate", "grate", "slate"];
+
+        let scored =
+            DistinctLetters.scores_with_worst_cases(&guesses, &possible, ScoreContext::default());
+        assert_eq!(
+            scored,
+            [
+                (5.0, Some(WorstCase::Exact(1))),
+                (3.0, Some(WorstCase::Exact(2)))
+            ]
+        );
+
+        for strategy in [&LetterFrequency as &dyn Strategy, &PositionalFrequency] {
+            let scores = strategy.scores(&guesses, &possible, ScoreContext::default());
+            let scored =
+                strategy.scores_with_worst_cases(&guesses, &possible, ScoreContext::default());
+            for ((guess, score), (scored, worst_case)) in guesses.iter().zip(scores).zip(scored) {
+                assert_eq!(scored, score);
+                assert_eq!(
+                    worst_case,
+                    Some(WorstCase::Exact(largest_group(guess, &possible)))
+                );
+            }
+        }
+    }
+}
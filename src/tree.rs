@@ -0,0 +1,429 @@
+//! Complete solving trees, worked out once for an answer list and looked up while playing.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::ops::ControlFlow;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Feedback, Guess, Index, ScoreContext, Strategy, Word, feedback, lookahead, pattern,
+    pattern_index,
+};
+
+// Start of every tree in the binary format.
+const MAGIC: &[u8; 4] = b"WHT1";
+
+// Probes the endgame lookahead considers besides the candidates, fewer than while playing since
+// it runs for every small group of the tree.
+const ENDGAME_PROBES: usize = 20;
+
+// Deeper trees in a binary file are rejected as broken instead of overflowing the stack.
+const MAX_DEPTH: usize = 64;
+
+/// Where a game stands in a [`Tree`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Next<'a> {
+    /// The word the tree guesses next.
+    Guess(&'a str),
+    /// A guess was all green.
+    Solved,
+    /// A guess or its feedback isn't in the tree.
+    Left,
+}
+
+/// What to guess for every feedback the game can give.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Tree {
+    /// The word to guess at this point.
+    pub guess: String,
+    /// Where to continue for every feedback but all green, keyed by [`pattern_index`].
+    pub next: BTreeMap<usize, Tree>,
+}
+
+impl Tree {
+    /// Builds the tree that opens with `opening` and solves every answer of the same length.
+    /// Besides the candidates, the words of `allowed` may be guessed to narrow them down.
+    ///
+    /// After the opening the best guess according to `strategy` is played, or the one with the
+    /// fewest expected guesses once [`lookahead::is_endgame`].
+    pub fn build(
+        answers: &[String],
+        allowed: &[String],
+        opening: &str,
+        strategy: &dyn Strategy,
+    ) -> Self {
+        let length = opening.chars().count();
+        let answers = Index::new(answers.to_vec());
+        let allowed = Index::new(allowed.to_vec());
+
+        Tree::grow(
+            &answers,
+            &allowed.collect(&allowed.of_length(length)),
+            &mut Vec::new(),
+            &answers.collect(&answers.of_length(length)),
+            opening.to_string(),
+            strategy,
+        )
+    }
+
+    fn grow(
+        answers: &Index,
+        probes: &[&str],
+        guesses: &mut Vec<Guess>,
+        possible: &[&str],
+        guess: String,
+        strategy: &dyn Strategy,
+    ) -> Self {
+        let length = guess.chars().count();
+        let guess_chars = guess.chars().collect::<Vec<_>>();
+
+        let mut patterns: BTreeMap<usize, Vec<Feedback>> = BTreeMap::new();
+        for answer in possible.iter().filter(|&&answer| answer != guess) {
+            let feedback = feedback(&guess_chars, &answer.chars().collect::<Vec<_>>());
+            patterns.entry(pattern_index(&feedback)).or_insert(feedback);
+        }
+
+        let mut next = BTreeMap::new();
+        for (pattern, feedback) in patterns {
+            guesses.push(Guess {
+                word: guess.clone(),
+                feedback,
+            });
+
+            let word = Word::from_guesses(length, guesses);
+            let remaining = answers.collect(&answers.matches(&word));
+            let child = best_guess(&remaining, probes, guesses, strategy);
+            next.insert(
+                pattern,
+                Tree::grow(answers, probes, guesses, &remaining, child, strategy),
+            );
+
+            guesses.pop();
+        }
+
+        Tree { guess, next }
+    }
+
+    /// Where `guesses` lead in the tree. Incomplete guesses are ignored.
+    pub fn next_guess(&self, guesses: &[Guess]) -> Next<'_> {
+        let mut node = self;
+        for guess in guesses.iter().filter(|guess| guess.is_complete()) {
+            if guess.word != node.guess {
+                return Next::Left;
+            }
+            if guess.feedback.iter().all(|&f| f == Feedback::Green) {
+                return Next::Solved;
+            }
+            match node.next.get(&pattern_index(&guess.feedback)) {
+                Some(child) => node = child,
+                None => return Next::Left,
+            }
+        }
+
+        Next::Guess(&node.guess)
+    }
+
+    /// Number of guesses the tree needs to find `answer`, `None` if it doesn't.
+    pub fn guesses_for(&self, answer: &str) -> Option<usize> {
+        let answer = answer.chars().collect::<Vec<_>>();
+        let mut node = self;
+        for guesses in 1.. {
+            let guess = node.guess.chars().collect::<Vec<_>>();
+            if guess == answer {
+                return Some(guesses);
+            }
+            node = node.next.get(&pattern_index(&feedback(&guess, &answer)))?;
+        }
+
+        None
+    }
+
+    /// Number of guesses in the tree.
+    pub fn size(&self) -> usize {
+        1 + self.next.values().map(Tree::size).sum::<usize>()
+    }
+
+    /// Most guesses the tree needs for any answer.
+    pub fn depth(&self) -> usize {
+        1 + self.next.values().map(Tree::depth).max().unwrap_or(0)
+    }
+
+    /// Writes the tree as JSON if `path` ends in `.json`, in the binary format otherwise.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        if is_json(path) {
+            serde_json::to_writer(&mut out, self)?;
+        } else {
+            self.write_binary(&mut out)?;
+        }
+
+        out.flush()
+    }
+
+    /// Reads a tree written by [`Tree::save`].
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut input = BufReader::new(File::open(path)?);
+        if is_json(path) {
+            Ok(serde_json::from_reader(input)?)
+        } else {
+            Tree::read_binary(&mut input)
+        }
+    }
+
+    /// The binary format: the magic bytes, every distinct word once, then the nodes depth
+    /// first as word index, number of children and the pattern before every child. Numbers
+    /// are little endian `u32`s, words are UTF-8 prefixed with their length in bytes.
+    pub fn write_binary(&self, out: &mut impl Write) -> io::Result<()> {
+        let mut words = Vec::new();
+        let mut index = HashMap::new();
+        self.collect_words(&mut words, &mut index);
+
+        out.write_all(MAGIC)?;
+        write_u32(out, words.len())?;
+        for w in words {
+            let len = u8::try_from(w.len()).map_err(|_| invalid("word too long"))?;
+            out.write_all(&[len])?;
+            out.write_all(w.as_bytes())?;
+        }
+
+        self.write_node(out, &index)
+    }
+
+    fn collect_words<'a>(&'a self, words: &mut Vec<&'a str>, index: &mut HashMap<&'a str, usize>) {
+        index.entry(&self.guess).or_insert_with(|| {
+            words.push(&self.guess);
+            words.len() - 1
+        });
+
+        for child in self.next.values() {
+            child.collect_words(words, index);
+        }
+    }
+
+    fn write_node(&self, out: &mut impl Write, index: &HashMap<&str, usize>) -> io::Result<()> {
+        write_u32(out, index[self.guess.as_str()])?;
+        write_u32(out, self.next.len())?;
+        for (&pattern, child) in &self.next {
+            write_u32(out, pattern)?;
+            child.write_node(out, index)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a tree written by [`Tree::write_binary`].
+    pub fn read_binary(input: &mut impl Read) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        input.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not a decision tree"));
+        }
+
+        let mut words = Vec::new();
+        for _ in 0..read_u32(input)? {
+            let mut len = [0; 1];
+            input.read_exact(&mut len)?;
+            let mut w = vec![0; len[0] as usize];
+            input.read_exact(&mut w)?;
+            words.push(String::from_utf8(w).map_err(|_| invalid("word isn't UTF-8"))?);
+        }
+
+        Tree::read_node(input, &words, 0)
+    }
+
+    fn read_node(input: &mut impl Read, words: &[String], depth: usize) -> io::Result<Self> {
+        if depth > MAX_DEPTH {
+            return Err(invalid("tree too deep"));
+        }
+
+        let guess = words
+            .get(read_u32(input)?)
+            .ok_or_else(|| invalid("unknown word index"))?
+            .clone();
+        let mut next = BTreeMap::new();
+        for _ in 0..read_u32(input)? {
+            let pattern = read_u32(input)?;
+            next.insert(pattern, Tree::read_node(input, words, depth + 1)?);
+        }
+
+        Ok(Tree { guess, next })
+    }
+}
+
+// Probes are only guessed when they beat every candidate, since a candidate may be the answer.
+// Guessing one of two candidates is never worse than a probe.
+// A probe that gives every candidate the same feedback, like a word already guessed, learns
+// nothing and would be offered again below it forever.
+fn best_guess(
+    possible: &[&str],
+    probes: &[&str],
+    guessed: &[Guess],
+    strategy: &dyn Strategy,
+) -> String {
+    let mut guesses = possible.to_vec();
+    if possible.len() > 2 {
+        let candidates = possible.iter().copied().collect::<HashSet<_>>();
+        guesses.extend(probes.iter().filter(|probe| !candidates.contains(*probe)));
+    }
+    // There is at least one candidate, the answer that gave this feedback.
+    let ranked = strategy
+        .rank(&guesses, possible, ScoreContext::default())
+        .into_iter()
+        .filter(|&(idx, _)| {
+            idx < possible.len()
+                || !guessed.iter().any(|guess| guess.word == guesses[idx])
+                    && splits(guesses[idx], possible)
+        })
+        .collect::<Vec<_>>();
+
+    if lookahead::is_endgame(possible.len()) {
+        let mut pool = possible.to_vec();
+        pool.extend(
+            ranked
+                .iter()
+                .filter(|&&(idx, _)| idx >= possible.len())
+                .take(ENDGAME_PROBES)
+                .map(|&(idx, _)| guesses[idx]),
+        );
+        if let Some(mut expected) =
+            lookahead::expected_guesses(&pool, possible, |_, _| ControlFlow::Continue(()))
+        {
+            return expected.swap_remove(0).0;
+        }
+    }
+
+    let best = ranked[0].1;
+    let (idx, _) = ranked
+        .iter()
+        .take_while(|&&(_, score)| score == best)
+        .find(|&&(idx, _)| idx < possible.len())
+        .unwrap_or(&ranked[0]);
+    guesses[*idx].to_string()
+}
+
+// Whether `guess` gives the candidates at least two different feedbacks.
+fn splits(guess: &str, possible: &[&str]) -> bool {
+    let guess = guess.chars().collect::<Vec<_>>();
+    let mut patterns = possible
+        .iter()
+        .map(|answer| pattern(&guess, &answer.chars().collect::<Vec<_>>()));
+    let first = patterns.next();
+    patterns.any(|pattern| Some(pattern) != first)
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, msg)
+}
+
+fn write_u32(out: &mut impl Write, n: usize) -> io::Result<()> {
+    let n = u32::try_from(n).map_err(|_| invalid("number too large"))?;
+    out.write_all(&n.to_le_bytes())
+}
+
+fn read_u32(input: &mut impl Read) -> io::Result<usize> {
+    let mut bytes = [0; 4];
+    input.read_exact(&mut bytes)?;
+
+    Ok(u32::from_le_bytes(bytes) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DistinctLetters, Entropy, distinct_letters, word_list};
+
+    fn small_tree() -> Tree {
+        let answers = word_list::default_answers()
+            .into_iter()
+            .filter(|w| w.starts_with("cr"))
+            .collect::<Vec<_>>();
+        Tree::build(&answers, &[], "crane", &Entropy)
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let tree = small_tree();
+        let mut bytes = Vec::new();
+        tree.write_binary(&mut bytes).unwrap();
+
+        assert_eq!(Tree::read_binary(&mut bytes.as_slice()).unwrap(), tree);
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(Tree::read_binary(&mut &b"WHPM\0\0\0\0"[..]).is_err());
+
+        let mut bytes = Vec::new();
+        small_tree().write_binary(&mut bytes).unwrap();
+        bytes.truncate(bytes.len() - 1);
+        assert!(Tree::read_binary(&mut bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn follows_feedback() {
+        let tree = small_tree();
+        let guess = |word: &str, answer: &str| {
+            let chars = |w: &str| w.chars().collect::<Vec<_>>();
+            Guess {
+                word: word.to_string(),
+                feedback: feedback(&chars(word), &chars(answer)),
+            }
+        };
+
+        let mut guesses = vec![guess("crane", "crust")];
+        while let Next::Guess(next) = tree.next_guess(&guesses) {
+            guesses.push(guess(next, "crust"));
+        }
+
+        assert_eq!(tree.next_guess(&guesses), Next::Solved);
+        assert_eq!(guesses.last().unwrap().word, "crust");
+        assert_eq!(tree.guesses_for("crust"), Some(guesses.len()));
+        assert!(guesses.len() <= tree.depth());
+
+        guesses[0].word = "slate".to_string();
+        assert_eq!(tree.next_guess(&guesses), Next::Left);
+    }
+
+    #[test]
+    fn probes_narrow_down_candidates() {
+        let answers = [
+            "fight", "light", "might", "night", "right", "sight", "tight",
+        ]
+        .map(String::from)
+        .to_vec();
+        let allowed = ["flint", "moths", "grass"].map(String::from).to_vec();
+
+        let plain = Tree::build(&answers, &[], "crane", &Entropy);
+        let probing = Tree::build(&answers, &allowed, "crane", &Entropy);
+        assert!(probing.depth() < plain.depth());
+        assert!(
+            answers
+                .iter()
+                .all(|answer| probing.guesses_for(answer).is_some())
+        );
+    }
+
+    #[test]
+    fn skips_probes_that_split_nothing() {
+        // The probe has more distinct letters than any answer and none of their letters.
+        let answers = word_list::default_answers()
+            .into_iter()
+            .filter(|w| !w.contains(['c', 'l', 'e', 'w', 's']) && distinct_letters(w) < 5)
+            .collect::<Vec<_>>();
+        let allowed = ["clews"].map(String::from).to_vec();
+
+        let tree = Tree::build(&answers, &allowed, &answers[0], &DistinctLetters);
+        assert!(
+            answers
+                .iter()
+                .all(|answer| tree.guesses_for(answer).is_some())
+        );
+    }
+}
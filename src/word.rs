@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use crate::{Feedback, Guess};
+
+/// Everything the feedback so far reveals about the answer.
+#[derive(Debug)]
+pub struct Word {
+    chars: Vec<Option<char>>,
+    wrong_pos: Vec<Vec<char>>,
+
+    // Minimum and maximum number of occurrences of every letter we know something about.
+    bounds: HashMap<char, (usize, usize)>,
+}
+
+impl Word {
+    /// No constraints yet for words of `length` letters.
+    pub fn new(length: usize) -> Self {
+        Word {
+            chars: vec![None; length],
+            wrong_pos: vec![Vec::new(); length],
+            bounds: HashMap::new(),
+        }
+    }
+
+    /// The word length these constraints are for.
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    // Known letters by position.
+    pub(crate) fn greens(&self) -> impl Iterator<Item = (usize, char)> + '_ {
+        self.chars
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, ch)| ch.map(|ch| (pos, ch)))
+    }
+
+    // Letters that can't be at a position.
+    pub(crate) fn excluded(&self) -> impl Iterator<Item = (usize, char)> + '_ {
+        self.wrong_pos
+            .iter()
+            .enumerate()
+            .flat_map(|(pos, chars)| chars.iter().map(move |&ch| (pos, ch)))
+    }
+
+    // Minimum and maximum count of every letter we know something about.
+    pub(crate) fn bounds(&self) -> impl Iterator<Item = (char, usize, usize)> + '_ {
+        self.bounds.iter().map(|(&ch, &(min, max))| (ch, min, max))
+    }
+
+    /// Combines the feedback of every complete guess.
+    pub fn from_guesses(length: usize, guesses: &[Guess]) -> Self {
+        let mut word = Word::new(length);
+        for guess in guesses.iter().filter(|guess| guess.is_complete()) {
+            word.add_guess(guess);
+        }
+
+        word
+    }
+
+    /// Adds the feedback of a single guess.
+    ///
+    /// A grey letter caps the count at the number of green and yellow copies in the same
+    /// guess, so a grey E next to a green E means "exactly one E" instead of "no E". Guesses
+    /// of another length say nothing about these words and are ignored.
+    pub fn add_guess(&mut self, guess: &Guess) {
+        if guess.feedback.len() != self.len() || guess.word.chars().count() != self.len() {
+            return;
+        }
+
+        let mut counts: HashMap<char, (usize, bool)> = HashMap::new();
+
+        for (idx, (ch, &feedback)) in guess.word.chars().zip(&guess.feedback).enumerate() {
+            let (count, capped) = counts.entry(ch).or_default();
+            match feedback {
+                Feedback::Green => {
+                    self.chars[idx] = Some(ch);
+                    *count += 1;
+                }
+                Feedback::Yellow => {
+                    self.wrong_pos[idx].push(ch);
+                    *count += 1;
+                }
+                Feedback::Grey => {
+                    self.wrong_pos[idx].push(ch);
+                    *capped = true;
+                }
+            }
+        }
+
+        for (ch, (count, capped)) in counts {
+            let (min, max) = self.bounds.entry(ch).or_insert((0, usize::MAX));
+            *min = (*min).max(count);
+            if capped {
+                *max = (*max).min(count);
+            }
+        }
+    }
+
+    /// Returns `w` if it could still be the answer.
+    pub fn filter(&self, w: &str) -> Option<String> {
+        let w_chars = w.chars().collect::<Vec<_>>();
+        if w_chars.len() != self.len() {
+            return None;
+        }
+
+        if self
+            .chars
+            .iter()
+            .enumerate()
+            .any(|(idx, ch)| ch.is_some_and(|ch| w_chars[idx] != ch))
+        {
+            return None;
+        }
+
+        if self
+            .wrong_pos
+            .iter()
+            .enumerate()
+            .any(|(idx, chars)| chars.contains(&w_chars[idx]))
+        {
+            return None;
+        }
+
+        if self.bounds.iter().any(|(&ch, &(min, max))| {
+            let count = w_chars.iter().filter(|&&c| c == ch).count();
+            count < min || count > max
+        }) {
+            return None;
+        }
+
+        Some(w.to_string())
+    }
+
+    /// Hard mode only requires green letters to stay in place and yellow letters to be reused.
+    /// Returns the hint `w` ignores, worded like the game does.
+    pub fn hard_mode_violation(&self, w: &str) -> Option<String> {
+        let w_chars = w.chars().collect::<Vec<_>>();
+        if w_chars.len() != self.len() {
+            return Some(format!("Guess must have {} letters", self.len()));
+        }
+
+        for (idx, ch) in self.chars.iter().enumerate() {
+            if let Some(ch) = ch
+                && w_chars[idx] != *ch
+            {
+                return Some(format!(
+                    "{} letter must be {}",
+                    ordinal(idx + 1),
+                    ch.to_uppercase()
+                ));
+            }
+        }
+
+        let mut bounds = self.bounds.iter().collect::<Vec<_>>();
+        bounds.sort_unstable();
+        for (&ch, &(min, _)) in bounds {
+            if w_chars.iter().filter(|&&c| c == ch).count() < min {
+                return Some(format!("Guess must contain {}", ch.to_uppercase()));
+            }
+        }
+
+        None
+    }
+
+    pub fn allows_in_hard_mode(&self, w: &str) -> bool {
+        self.hard_mode_violation(w).is_none()
+    }
+}
+
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+
+    format!("{n}{suffix}")
+}
+
+/// Checks every guess against the allowed words and, in hard mode, against the hints revealed
+/// by the guesses before it. `allowed` must be sorted; an empty list skips the word check.
+pub fn guess_warnings(
+    length: usize,
+    guesses: &[Guess],
+    allowed: &[String],
+    hard_mode: bool,
+) -> Vec<Option<String>> {
+    guesses
+        .iter()
+        .enumerate()
+        .map(|(idx, guess)| {
+            if !guess.is_complete() {
+                return None;
+            }
+
+            if !allowed.is_empty() && allowed.binary_search(&guess.word).is_err() {
+                return Some("Not in word list".to_string());
+            }
+
+            if !hard_mode {
+                return None;
+            }
+
+            Word::from_guesses(length, &guesses[..idx]).hard_mode_violation(&guess.word)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(guesses: &[&str]) -> Word {
+        let guesses = guesses
+            .iter()
+            .map(|guess| Guess::parse(guess).unwrap())
+            .collect::<Vec<_>>();
+        Word::from_guesses(5, &guesses)
+    }
+
+    #[test]
+    fn grey_copy_caps_the_count() {
+        // A grey E next to a green E means exactly one E.
+        let word = word(&["speed:gbgbb"]);
+        assert!(word.filter("steal").is_some());
+        assert!(word.filter("steel").is_none());
+        assert!(word.filter("stale").is_none());
+    }
+
+    #[test]
+    fn yellow_letters_set_a_minimum() {
+        let word = word(&["eerie:ybbbb"]);
+        // Exactly one E, and not where any of the three were guessed.
+        assert!(word.filter("ahead").is_some());
+        assert!(word.filter("tweak").is_some());
+        assert!(word.filter("cable").is_none());
+        assert!(word.filter("cloud").is_none());
+        assert!(word.filter("geese").is_none());
+        assert!(word.filter("early").is_none());
+    }
+
+    #[test]
+    fn wrong_length_never_matches() {
+        let word = word(&["crane:bbbbb"]);
+        assert!(word.filter("box").is_none());
+        assert!(word.filter("bloody").is_none());
+    }
+
+    #[test]
+    fn other_lengths_are_ignored() {
+        let word = word(&["abcdef:gggggg", "crane:bbbbb"]);
+        assert!(word.filter("abcde").is_none());
+        assert!(word.filter("tulip").is_some());
+    }
+
+    #[test]
+    fn hard_mode_reuses_hints() {
+        let word = word(&["crane:gybbb"]);
+        assert_eq!(
+            word.hard_mode_violation("ports").as_deref(),
+            Some("1st letter must be C")
+        );
+        assert_eq!(
+            word.hard_mode_violation("chops").as_deref(),
+            Some("Guess must contain R")
+        );
+        assert!(word.allows_in_hard_mode("curds"));
+    }
+}
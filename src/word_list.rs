@@ -0,0 +1,248 @@
+//! Loading and cleaning up word lists.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+use crate::{MAX_LENGTH, MIN_LENGTH};
+
+// Used until another answer list is opened.
+const DEFAULT_WORDS: &str = include_str!("../words.txt");
+
+// How many rejected words are listed by name in the summary.
+const REPORT_EXAMPLES: usize = 5;
+
+/// Whether accented letters count as letters of their own.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Accents {
+    /// `ñ` and `n` are different letters, like in Spanish or German.
+    #[default]
+    Keep,
+    /// `ã` is just an `a`, like in most Portuguese versions of the game.
+    Strip,
+}
+
+impl Accents {
+    /// Both options, the default first.
+    pub const ALL: [Accents; 2] = [Accents::Keep, Accents::Strip];
+
+    /// Short name used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Accents::Keep => "keep",
+            Accents::Strip => "strip",
+        }
+    }
+
+    /// Looks up an option by its [`Accents::name`].
+    pub fn parse(name: &str) -> Option<Self> {
+        Accents::ALL
+            .into_iter()
+            .find(|accents| accents.name() == name)
+    }
+}
+
+/// Lowercases `w` and composes every letter into a single `char`, so each tile is one `char`
+/// however the text was typed or saved. Accents are removed if `accents` says so and the Greek
+/// final sigma becomes `σ`, which is the same tile.
+pub fn fold(w: &str, accents: Accents) -> String {
+    w.to_lowercase()
+        .nfd()
+        .filter(|&ch| accents == Accents::Keep || !is_combining_mark(ch))
+        .nfc()
+        .map(|ch| if ch == 'ς' { 'σ' } else { ch })
+        .collect()
+}
+
+/// What happened to the lines of a word list while it was loaded.
+#[derive(Default, Debug)]
+pub struct LoadReport {
+    pub accepted: usize,
+    pub bad_length: Vec<String>,
+    pub non_alphabetic: Vec<String>,
+    /// Words with a letter and mark that have no single `char`, which wouldn't fit on one tile.
+    pub split_letters: Vec<String>,
+    pub duplicates: usize,
+}
+
+impl LoadReport {
+    /// A human readable summary, one line per kind of problem.
+    pub fn summary(&self) -> String {
+        let mut summary = format!("Loaded {} words", self.accepted);
+
+        if !self.bad_length.is_empty() {
+            summary += &format!(
+                "\nRejected {} not {MIN_LENGTH} to {MAX_LENGTH} letters long: {}",
+                self.bad_length.len(),
+                examples(&self.bad_length)
+            );
+        }
+
+        if !self.non_alphabetic.is_empty() {
+            summary += &format!(
+                "\nRejected {} with non-letters: {}",
+                self.non_alphabetic.len(),
+                examples(&self.non_alphabetic)
+            );
+        }
+
+        if !self.split_letters.is_empty() {
+            summary += &format!(
+                "\nRejected {} with letters that don't fit on one tile: {}",
+                self.split_letters.len(),
+                examples(&self.split_letters)
+            );
+        }
+
+        if self.duplicates > 0 {
+            summary += &format!("\nRemoved {} duplicates", self.duplicates);
+        }
+
+        summary
+    }
+}
+
+fn examples(words: &[String]) -> String {
+    let mut examples = words
+        .iter()
+        .take(REPORT_EXAMPLES)
+        .map(|w| format!("\"{w}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if words.len() > REPORT_EXAMPLES {
+        examples += ", …";
+    }
+
+    examples
+}
+
+/// Trims and [`fold`]s every line, then drops blank lines, words of unsupported length, words
+/// containing anything but letters and duplicates. The order of the remaining words is kept.
+pub fn normalize<I: IntoIterator<Item = String>>(
+    lines: I,
+    accents: Accents,
+) -> (Vec<String>, LoadReport) {
+    let mut report = LoadReport::default();
+    let mut words = Vec::new();
+
+    for line in lines {
+        let w = fold(line.trim(), accents);
+        if w.is_empty() {
+            continue;
+        }
+
+        if w.chars().any(is_combining_mark) {
+            report.split_letters.push(w);
+        } else if !w.chars().all(char::is_alphabetic) {
+            report.non_alphabetic.push(w);
+        } else if !(MIN_LENGTH..=MAX_LENGTH).contains(&w.chars().count()) {
+            report.bad_length.push(w);
+        } else {
+            words.push(w);
+        }
+    }
+
+    let total = words.len();
+    let mut seen = HashSet::with_capacity(total);
+    words.retain(|w| seen.insert(w.clone()));
+    report.duplicates = total - words.len();
+
+    report.accepted = words.len();
+    (words, report)
+}
+
+/// The answer list built into the binary.
+pub fn default_answers() -> Vec<String> {
+    normalize(DEFAULT_WORDS.lines().map(String::from), Accents::Keep).0
+}
+
+/// Reads and [`normalize`]s the word list at `path`, one word per line.
+pub fn read(path: &Path, accents: Accents) -> Option<(Vec<String>, LoadReport)> {
+    let file = File::open(path).ok()?;
+
+    Some(normalize(
+        BufReader::new(file).lines().map_while(Result::ok),
+        accents,
+    ))
+}
+
+/// Every word that may be guessed, sorted so guesses can be looked up quickly.
+pub fn merge(answers: &[String], guesses: &[String]) -> Vec<String> {
+    let mut allowed = answers.iter().chain(guesses).cloned().collect::<Vec<_>>();
+    allowed.sort_unstable();
+    allowed.dedup();
+    allowed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(list: &[&str]) -> Vec<String> {
+        list.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn normalize_cleans_up_and_reports_lines() {
+        let (words, report) = normalize(
+            lines(&[
+                "crane\r",
+                "  Slate ",
+                "",
+                "CRANE",
+                "cat",
+                "abcdefghijkl",
+                "don't",
+                "ab12",
+                "slate",
+            ]),
+            Accents::Keep,
+        );
+
+        assert_eq!(words, ["crane", "slate"]);
+        assert_eq!(report.accepted, 2);
+        assert_eq!(report.bad_length, ["cat", "abcdefghijkl"]);
+        assert_eq!(report.non_alphabetic, ["don't", "ab12"]);
+        assert!(report.split_letters.is_empty());
+        assert_eq!(report.duplicates, 2);
+        assert_eq!(
+            report.summary(),
+            "Loaded 2 words\n\
+             Rejected 2 not 4 to 11 letters long: \"cat\", \"abcdefghijkl\"\n\
+             Rejected 2 with non-letters: \"don't\", \"ab12\"\n\
+             Removed 2 duplicates"
+        );
+    }
+
+    #[test]
+    fn fold_makes_one_char_per_tile() {
+        // "ação" typed as letters followed by combining marks.
+        let decomposed = "Ac\u{327}a\u{303}o";
+        assert_eq!(decomposed.chars().count(), 6);
+        assert_eq!(fold(decomposed, Accents::Keep), "ação");
+        assert_eq!(fold(decomposed, Accents::Keep).chars().count(), 4);
+
+        assert_eq!(fold("AÇÃO", Accents::Strip), "acao");
+        assert_eq!(fold("Niño", Accents::Keep), "niño");
+        assert_eq!(fold("Niño", Accents::Strip), "nino");
+
+        assert_eq!(fold("ΛΈΞΙΣ", Accents::Keep), "λέξισ");
+        assert_eq!(fold("λέξις", Accents::Keep), "λέξισ");
+        assert_eq!(fold("Straße", Accents::Keep), "straße");
+        assert_eq!(fold("ПРИВЕТ", Accents::Keep), "привет");
+    }
+
+    #[test]
+    fn normalize_rejects_letters_without_a_char() {
+        // Devanagari ka with a virama has no precomposed form.
+        let (words, report) = normalize(lines(&["क\u{94d}षम", "ação"]), Accents::Keep);
+
+        assert_eq!(words, ["ação"]);
+        assert_eq!(report.split_letters, ["क\u{94d}षम"]);
+    }
+}